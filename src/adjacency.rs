@@ -0,0 +1,313 @@
+//! Backend agnostic view over the graph implementations, so
+//! that algorithms can be written once and run on either the
+//! adjacency-list or the adjacency-matrix graph.
+use std::{fmt::Debug, hash::Hash};
+
+use crate::{list_graph, mtx_graph};
+use crate::mtx_graph::graph::GraphIdx;
+
+/// Common read-only interface over both graph backends.
+///
+/// Algorithms address nodes by their dense *position*
+/// (`0..node_count()`) and translate back to the backend's
+/// native index with `node_id` when producing results.
+pub trait Adjacency {
+    /// The backend's native node index.
+    type NodeId: Copy + Eq + Hash + Debug;
+    /// The backend's edge weight type.
+    type Weight: Copy;
+    /// The data held by every node.
+    type Node;
+
+    /// Number of nodes in the graph.
+    fn node_count(&self) -> usize;
+
+    /// Whether edges are one-way.
+    fn is_directed(&self) -> bool;
+
+    /// Native index of the node at `pos`.
+    fn node_id(&self, pos: usize) -> Self::NodeId;
+
+    /// Position of the node with native index `id`.
+    fn node_pos(&self, id: Self::NodeId) -> usize;
+
+    /// Data of the node at `pos`.
+    fn node_data(&self, pos: usize) -> &Self::Node;
+
+    /// Outgoing edges of the node at `pos` as
+    /// `(target position, weight)` pairs.
+    fn out_edges(&self, pos: usize) -> Vec<(usize, Self::Weight)>;
+
+    /// Positions of the nodes reachable over a single
+    /// outgoing edge of `pos`.
+    fn out_neighbors(&self, pos: usize) -> Vec<usize> {
+        self.out_edges(pos).into_iter().map(|(n, _)| n).collect()
+    }
+}
+
+/// Builds symmetric neighbour lists for `graph`, following
+/// directed edges both ways. Parallel edges are collapsed.
+pub(crate) fn undirected_adjacency<G: Adjacency>(graph: &G) -> Vec<Vec<usize>> {
+    let n = graph.node_count();
+    let mut adj = vec![Vec::new(); n];
+    for (u, list) in adj.iter_mut().enumerate() {
+        list.extend(graph.out_neighbors(u));
+    }
+    if graph.is_directed() {
+        for u in 0..n {
+            for v in graph.out_neighbors(u) {
+                adj[v].push(u);
+            }
+        }
+    }
+    for list in adj.iter_mut() {
+        list.sort_unstable();
+        list.dedup();
+    }
+
+    adj
+}
+
+/// Symmetric neighbour lists like `undirected_adjacency`, but
+/// keeping parallel edges, for algorithms on multigraphs.
+pub(crate) fn multigraph_adjacency<G: Adjacency>(graph: &G) -> Vec<Vec<usize>> {
+    let n = graph.node_count();
+    let mut adj: Vec<Vec<usize>> = (0..n).map(|u| graph.out_neighbors(u)).collect();
+    if graph.is_directed() {
+        for u in 0..n {
+            for v in graph.out_neighbors(u) {
+                adj[v].push(u);
+            }
+        }
+    }
+
+    adj
+}
+
+/// Every edge once, as `(from, to, weight)`. Undirected edges are
+/// listed by both endpoints, so only the copy from the smaller one
+/// is kept; self loops are listed twice by the list backend and
+/// once by the matrix one, hence the rounding up.
+pub(crate) fn edge_list<G: Adjacency>(graph: &G) -> Vec<(usize, usize, G::Weight)> {
+    let mut edges = Vec::new();
+    for u in 0..graph.node_count() {
+        let out = graph.out_edges(u);
+        if graph.is_directed() {
+            edges.extend(out.into_iter().map(|(v, w)| (u, v, w)));
+        } else {
+            let loops: Vec<G::Weight> = out.iter().filter(|e| e.0 == u).map(|e| e.1).collect();
+            edges.extend(out.into_iter().filter(|e| u < e.0).map(|(v, w)| (u, v, w)));
+            edges.extend(loops.into_iter().step_by(2).map(|w| (u, u, w)));
+        }
+    }
+    edges
+}
+
+/// Drops self loops from neighbour lists.
+pub(crate) fn without_loops(mut adj: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    for (v, list) in adj.iter_mut().enumerate() {
+        list.retain(|&u| u != v);
+    }
+    adj
+}
+
+/// Bucket sorts the nodes by degree and peels them in order
+/// (Batagelj-Zaversnik), returning the core number of every
+/// node and the peeling order. Self loops
+/// in `adj` are skipped.
+pub(crate) fn bucket_cores(adj: &[Vec<usize>]) -> (Vec<usize>, Vec<usize>) {
+    let n = adj.len();
+    let mut degree: Vec<usize> = adj
+        .iter()
+        .enumerate()
+        .map(|(v, list)| list.iter().filter(|&&u| u != v).count())
+        .collect();
+    let max = degree.iter().copied().max().unwrap_or(0);
+
+    // `start[d]` is where nodes of degree d begin in `vert`.
+    let mut start = vec![0; max + 2];
+    for &d in &degree {
+        start[d + 1] += 1;
+    }
+    for d in 1..start.len() {
+        start[d] += start[d - 1];
+    }
+    let mut vert = vec![0; n];
+    let mut pos = vec![0; n];
+    let mut next = start.clone();
+    for v in 0..n {
+        pos[v] = next[degree[v]];
+        vert[pos[v]] = v;
+        next[degree[v]] += 1;
+    }
+
+    for i in 0..n {
+        let v = vert[i];
+        for &u in &adj[v] {
+            if degree[u] > degree[v] {
+                // Move u to the front of its bucket, then shrink
+                // the bucket past it.
+                let du = degree[u];
+                let first = start[du];
+                let w = vert[first];
+                vert.swap(pos[u], first);
+                pos[w] = pos[u];
+                pos[u] = first;
+                start[du] += 1;
+                degree[u] -= 1;
+            }
+        }
+    }
+
+    (degree, vert)
+}
+
+/// Removal order of repeatedly taking a minimum degree node,
+/// i.e. a degeneracy ordering.
+pub(crate) fn degeneracy_order(adj: &[Vec<usize>]) -> Vec<usize> {
+    bucket_cores(adj).1
+}
+
+/// Marker trait that lets algorithms ask a `Directed` /
+/// `Undirected` type parameter which one it is.
+pub trait EdgeType {
+    const DIRECTED: bool;
+}
+
+impl EdgeType for list_graph::graph::Directed {
+    const DIRECTED: bool = true;
+}
+
+impl EdgeType for list_graph::graph::Undirected {
+    const DIRECTED: bool = false;
+}
+
+impl EdgeType for mtx_graph::graph::Directed {
+    const DIRECTED: bool = true;
+}
+
+impl EdgeType for mtx_graph::graph::Undirected {
+    const DIRECTED: bool = false;
+}
+
+/// Numeric edge weights that algorithms can do arithmetic on.
+pub trait Measure: Copy {
+    fn to_i64(self) -> i64;
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_measure {
+    ($($t:ty),*) => {
+        $(
+            impl Measure for $t {
+                fn to_i64(self) -> i64 {
+                    self as i64
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_measure!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+impl<V, D, E> Adjacency for list_graph::graph::Graph<V, D, E>
+where
+    D: EdgeType,
+    E: Copy,
+{
+    type NodeId = usize;
+    type Weight = E;
+    type Node = V;
+
+    fn node_count(&self) -> usize {
+        self.len()
+    }
+
+    fn is_directed(&self) -> bool {
+        D::DIRECTED
+    }
+
+    fn node_id(&self, pos: usize) -> usize {
+        pos
+    }
+
+    fn node_pos(&self, id: usize) -> usize {
+        id
+    }
+
+    fn node_data(&self, pos: usize) -> &V {
+        &self[pos].data
+    }
+
+    fn out_edges(&self, pos: usize) -> Vec<(usize, E)> {
+        self.edges(pos).iter().map(|e| (e.next, e.weight)).collect()
+    }
+}
+
+impl<T, D, W> Adjacency for mtx_graph::graph::Graph<T, D, W>
+where
+    T: Hash + Eq + Clone,
+    D: Clone + EdgeType,
+    W: Clone,
+{
+    type NodeId = GraphIdx;
+    type Weight = usize;
+    type Node = T;
+
+    fn node_count(&self) -> usize {
+        self.nodes()
+    }
+
+    fn is_directed(&self) -> bool {
+        D::DIRECTED
+    }
+
+    fn node_id(&self, pos: usize) -> GraphIdx {
+        GraphIdx(pos)
+    }
+
+    fn node_pos(&self, id: GraphIdx) -> usize {
+        id.0
+    }
+
+    fn node_data(&self, pos: usize) -> &T {
+        self.get_node(GraphIdx(pos))
+    }
+
+    fn out_edges(&self, pos: usize) -> Vec<(usize, usize)> {
+        self.edges(GraphIdx(pos))
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| **w > 0)
+            .map(|(i, w)| (i, *w))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backends_agree() {
+        let mut lg = list_graph::graph::Graph::<(), list_graph::graph::Directed>::new();
+        let a = lg.add_node(());
+        let b = lg.add_node(());
+        lg.add_edge(a, b, 3);
+
+        let mut mg = mtx_graph::graph::Graph::<u8, mtx_graph::graph::Directed, mtx_graph::graph::Weighted>::default();
+        let x = mg.add_node(0);
+        let y = mg.add_node(1);
+        mg.add_edge(x, y, 3);
+
+        assert!(lg.is_directed() && mg.is_directed());
+        assert_eq!(lg.node_count(), mg.node_count());
+        assert_eq!(lg.out_edges(0), vec![(1, 3)]);
+        assert_eq!(mg.out_edges(0), vec![(1, 3)]);
+        assert!(mg.out_edges(1).is_empty());
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::VecDeque;
+
+use crate::adjacency::Adjacency;
+use crate::lcg::Lcg;
+
+use super::distance::neighbours;
+
+/// Betweenness of every node: the number of shortest paths
+/// between other pairs that pass through it, split evenly among
+/// equally short paths. Computed with Brandes' accumulation,
+/// ignoring weights. With `normalized` the scores are divided
+/// by the number of pairs not involving the node.
+/// Time complexity:
+///   O(n * m)
+pub fn betweenness_centrality<G: Adjacency>(graph: &G, normalized: bool) -> Vec<f64> {
+    let adj = neighbours(graph);
+    let sources: Vec<usize> = (0..adj.len()).collect();
+    brandes(&adj, &sources, graph.is_directed(), normalized)
+}
+
+/// Estimates betweenness from the shortest paths out of
+/// `samples` distinct source nodes drawn with a generator
+/// seeded by `seed`, scaled up to the whole graph. Asking for
+/// as many samples as nodes gives the exact scores.
+/// Time complexity:
+///   O(samples * m)
+pub fn sampled_betweenness_centrality<G: Adjacency>(
+    graph: &G,
+    samples: usize,
+    seed: u64,
+    normalized: bool,
+) -> Vec<f64> {
+    let adj = neighbours(graph);
+    let n = adj.len();
+    let k = samples.min(n);
+
+    // Partial Fisher-Yates shuffle picks k distinct sources.
+    let mut rng = Lcg::new(seed);
+    let mut nodes: Vec<usize> = (0..n).collect();
+    for i in 0..k {
+        let j = i + rng.below(n - i);
+        nodes.swap(i, j);
+    }
+    nodes.truncate(k);
+
+    let mut scores = brandes(&adj, &nodes, graph.is_directed(), normalized);
+    if k > 0 {
+        let factor = n as f64 / k as f64;
+        scores.iter_mut().for_each(|s| *s *= factor);
+    }
+    scores
+}
+
+fn brandes(adj: &[Vec<usize>], sources: &[usize], directed: bool, normalized: bool) -> Vec<f64> {
+    let n = adj.len();
+    let mut score = vec![0.0; n];
+    for &s in sources {
+        let mut order = Vec::with_capacity(n);
+        let mut preds: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut paths = vec![0.0f64; n];
+        let mut dist = vec![usize::MAX; n];
+        paths[s] = 1.0;
+        dist[s] = 0;
+        let mut queue = VecDeque::from(vec![s]);
+        while let Some(u) = queue.pop_front() {
+            order.push(u);
+            for &v in &adj[u] {
+                if dist[v] == usize::MAX {
+                    dist[v] = dist[u] + 1;
+                    queue.push_back(v);
+                }
+                if dist[v] == dist[u] + 1 {
+                    paths[v] += paths[u];
+                    preds[v].push(u);
+                }
+            }
+        }
+
+        let mut delta = vec![0.0; n];
+        for &w in order.iter().rev() {
+            for &v in &preds[w] {
+                delta[v] += paths[v] / paths[w] * (1.0 + delta[w]);
+            }
+            if w != s {
+                score[w] += delta[w];
+            }
+        }
+    }
+
+    // Undirected paths were counted from both ends.
+    let mut factor = if directed { 1.0 } else { 0.5 };
+    if normalized && n > 2 {
+        let pairs = ((n - 1) * (n - 2)) as f64;
+        factor = if directed { 1.0 / pairs } else { 2.0 * factor / pairs };
+    }
+    score.iter_mut().for_each(|s| *s *= factor);
+
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Graph, Undirected};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn path_and_star() {
+        let mut g = Graph::<_, Undirected, ()>::new();
+        for i in 0..4 {
+            g.add_node(());
+            if i > 0 {
+                g.add_edge(i - 1, i, ());
+            }
+        }
+        assert_eq!(betweenness_centrality(&g, false), vec![0.0, 2.0, 2.0, 0.0]);
+        assert_eq!(sampled_betweenness_centrality(&g, 4, 9, false), vec![0.0, 2.0, 2.0, 0.0]);
+
+        let mut star = mtx::Graph::<u8, mtx::Undirected, mtx::Unweighted>::default();
+        let ids: Vec<_> = (0..5).map(|i| star.add_node(i)).collect();
+        for &leaf in &ids[1..] {
+            star.add_edge(ids[0], leaf);
+        }
+        assert_eq!(betweenness_centrality(&star, true), vec![1.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn split_paths() {
+        // Two equally short routes around a square.
+        let mut g = Graph::<_, Undirected, ()>::new();
+        for _ in 0..4 {
+            g.add_node(());
+        }
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 0)] {
+            g.add_edge(a, b, ());
+        }
+        assert_eq!(betweenness_centrality(&g, false), vec![0.5; 4]);
+    }
+}
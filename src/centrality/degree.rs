@@ -0,0 +1,60 @@
+use crate::adjacency::Adjacency;
+
+/// Fraction of the other nodes each node is adjacent to. On a
+/// directed graph in and out edges both count, so scores can
+/// exceed one.
+/// Time complexity:
+///   O(n + m)
+pub fn degree_centrality<G: Adjacency>(graph: &G) -> Vec<f64> {
+    if graph.is_directed() {
+        let ins = in_degree_centrality(graph);
+        let outs = out_degree_centrality(graph);
+        ins.into_iter().zip(outs).map(|(i, o)| i + o).collect()
+    } else {
+        out_degree_centrality(graph)
+    }
+}
+
+/// Number of incoming edges over `n - 1`.
+pub fn in_degree_centrality<G: Adjacency>(graph: &G) -> Vec<f64> {
+    let n = graph.node_count();
+    let mut degree = vec![0usize; n];
+    for v in (0..n).flat_map(|u| graph.out_neighbors(u)) {
+        degree[v] += 1;
+    }
+    scale(degree)
+}
+
+/// Number of outgoing edges over `n - 1`.
+pub fn out_degree_centrality<G: Adjacency>(graph: &G) -> Vec<f64> {
+    let degree = (0..graph.node_count())
+        .map(|u| graph.out_neighbors(u).len())
+        .collect();
+    scale(degree)
+}
+
+fn scale(degree: Vec<usize>) -> Vec<f64> {
+    let s = if degree.len() > 1 { 1.0 / (degree.len() - 1) as f64 } else { 1.0 };
+    degree.into_iter().map(|d| d as f64 * s).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph};
+
+    use super::*;
+
+    #[test]
+    fn directed_star() {
+        let mut g = Graph::<(), Directed, ()>::new();
+        for _ in 0..5 {
+            g.add_node(());
+        }
+        for leaf in 1..5 {
+            g.add_edge(0, leaf, ());
+        }
+        assert_eq!(out_degree_centrality(&g), vec![1.0, 0.0, 0.0, 0.0, 0.0]);
+        assert_eq!(in_degree_centrality(&g), vec![0.0, 0.25, 0.25, 0.25, 0.25]);
+        assert_eq!(degree_centrality(&g)[1], 0.25);
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::VecDeque;
+
+use crate::adjacency::Adjacency;
+
+/// Closeness of every node: the inverse of its average distance
+/// to the nodes it reaches, following edge direction and
+/// ignoring weights. Nodes that reach only part of the graph
+/// are scaled by the fraction they reach (Wasserman and Faust),
+/// so scores stay comparable across components.
+/// Time complexity:
+///   O(n * (n + m))
+pub fn closeness_centrality<G: Adjacency>(graph: &G) -> Vec<f64> {
+    let n = graph.node_count();
+    let adj = neighbours(graph);
+    (0..n)
+        .map(|s| {
+            let dist = bfs(&adj, s);
+            let reached = dist.iter().filter(|d| d.is_some()).count() - 1;
+            let total: usize = dist.iter().flatten().sum();
+            if total == 0 {
+                return 0.0;
+            }
+            let r = reached as f64;
+            (r / total as f64) * (r / (n - 1) as f64)
+        })
+        .collect()
+}
+
+/// Harmonic centrality: the sum of inverse distances from each
+/// node to every other one, where unreachable nodes add zero.
+/// Time complexity:
+///   O(n * (n + m))
+pub fn harmonic_centrality<G: Adjacency>(graph: &G) -> Vec<f64> {
+    let adj = neighbours(graph);
+    (0..adj.len())
+        .map(|s| {
+            bfs(&adj, s)
+                .into_iter()
+                .flatten()
+                .filter(|&d| d > 0)
+                .map(|d| 1.0 / d as f64)
+                .sum()
+        })
+        .collect()
+}
+
+pub(crate) fn neighbours<G: Adjacency>(graph: &G) -> Vec<Vec<usize>> {
+    (0..graph.node_count()).map(|u| graph.out_neighbors(u)).collect()
+}
+
+fn bfs(adj: &[Vec<usize>], s: usize) -> Vec<Option<usize>> {
+    let mut dist = vec![None; adj.len()];
+    dist[s] = Some(0);
+    let mut queue = VecDeque::from(vec![s]);
+    while let Some(u) = queue.pop_front() {
+        let d = dist[u].unwrap() + 1;
+        for &v in &adj[u] {
+            if dist[v].is_none() {
+                dist[v] = Some(d);
+                queue.push_back(v);
+            }
+        }
+    }
+    dist
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Graph, Undirected};
+
+    use super::*;
+
+    fn path(n: usize) -> Graph<(), Undirected, ()> {
+        let mut g = Graph::<_, Undirected, ()>::new();
+        for i in 0..n {
+            g.add_node(());
+            if i > 0 {
+                g.add_edge(i - 1, i, ());
+            }
+        }
+        g
+    }
+
+    #[test]
+    fn path_scores() {
+        let g = path(3);
+        assert_eq!(closeness_centrality(&g), vec![2.0 / 3.0, 1.0, 2.0 / 3.0]);
+        assert_eq!(harmonic_centrality(&g), vec![1.5, 2.0, 1.5]);
+    }
+
+    #[test]
+    fn disconnected_scaling() {
+        let mut g = path(2);
+        g.add_node(());
+        // One of two other nodes at distance one.
+        assert_eq!(closeness_centrality(&g), vec![0.5, 0.5, 0.0]);
+        assert_eq!(harmonic_centrality(&g)[2], 0.0);
+    }
+}
@@ -0,0 +1,8 @@
+//! Node centrality scores. Every measure returns one score per
+//! node, indexed by position (`usize::from` of a `GraphIdx` on
+//! the matrix backend).
+pub mod betweenness;
+pub mod degree;
+pub mod distance;
+pub mod pagerank;
+pub mod spectral;
@@ -0,0 +1,165 @@
+use crate::adjacency::Adjacency;
+use crate::list_graph::graph::{Directed, Graph};
+
+use super::distance::neighbours;
+use super::pagerank::{PageRank, PageRankError, PageRankScores};
+
+/// Eigenvector centrality: scores proportional to the sum of the
+/// scores of the nodes linking in, found by power iteration on
+/// `A + I` so bipartite graphs do not oscillate. Scores have
+/// unit Euclidean norm. Returns `None` if the iteration has not
+/// moved less than `n * tolerance` within `max_iter` rounds.
+/// Time complexity:
+///   O(max_iter * (n + m))
+pub fn eigenvector_centrality<G: Adjacency>(graph: &G, max_iter: usize, tolerance: f64) -> Option<Vec<f64>> {
+    let adj = neighbours(graph);
+    let n = adj.len();
+    if n == 0 {
+        return Some(Vec::new());
+    }
+
+    let mut x = vec![1.0 / n as f64; n];
+    for _ in 0..max_iter {
+        let mut next = x.clone();
+        for (u, list) in adj.iter().enumerate() {
+            for &v in list {
+                next[v] += x[u];
+            }
+        }
+        normalize(&mut next);
+        if converged(&x, &next, tolerance) {
+            return Some(next);
+        }
+        x = next;
+    }
+    None
+}
+
+/// Katz centrality: `x = alpha * A^T x + beta`, so every node
+/// gets `beta` plus the attenuated scores of the nodes linking
+/// in. `alpha` must stay below the inverse of the largest
+/// eigenvalue for the iteration to converge. Scores have unit
+/// Euclidean norm.
+/// Time complexity:
+///   O(max_iter * (n + m))
+pub fn katz_centrality<G: Adjacency>(
+    graph: &G,
+    alpha: f64,
+    beta: f64,
+    max_iter: usize,
+    tolerance: f64,
+) -> Option<Vec<f64>> {
+    let adj = neighbours(graph);
+    let n = adj.len();
+    if n == 0 {
+        return Some(Vec::new());
+    }
+
+    let mut x = vec![0.0; n];
+    for _ in 0..max_iter {
+        let mut next = vec![beta; n];
+        for (u, list) in adj.iter().enumerate() {
+            for &v in list {
+                next[v] += alpha * x[u];
+            }
+        }
+        if converged(&x, &next, tolerance) {
+            normalize(&mut next);
+            return Some(next);
+        }
+        x = next;
+    }
+    None
+}
+
+/// PageRank with damping factor `damping`: a random surfer
+/// follows a uniformly chosen out edge with that probability
+/// and jumps to a uniformly chosen node otherwise, or always
+/// when stuck at a node without out edges. Scores sum to one.
+/// Runs `PageRank` with its default tolerance and iteration
+/// limit on a copy of the graph with undirected edges followed
+/// both ways, so check `converged` on the result; use `PageRank`
+/// directly for weights and personalization.
+/// Time complexity:
+///   O(iterations * (n + m))
+pub fn pagerank_centrality<G: Adjacency>(graph: &G, damping: f64) -> Result<PageRankScores, PageRankError> {
+    let mut links = Graph::<(), Directed, f64>::new();
+    for _ in 0..graph.node_count() {
+        links.add_node(());
+    }
+    for (u, list) in neighbours(graph).into_iter().enumerate() {
+        for v in list {
+            links.add_edge(u, v, 1.0);
+        }
+    }
+    PageRank::new().damping(damping).run(&links)
+}
+
+fn normalize(x: &mut [f64]) {
+    let norm = x.iter().map(|v| v * v).sum::<f64>().sqrt();
+    if norm > 0.0 {
+        x.iter_mut().for_each(|v| *v /= norm);
+    }
+}
+
+fn converged(old: &[f64], new: &[f64], tolerance: f64) -> bool {
+    let diff: f64 = old.iter().zip(new).map(|(a, b)| (a - b).abs()).sum();
+    diff < old.len() as f64 * tolerance
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::Undirected;
+
+    use super::*;
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-6)
+    }
+
+    #[test]
+    fn symmetric_graphs() {
+        let mut cycle = Graph::<_, Undirected, ()>::new();
+        for _ in 0..4 {
+            cycle.add_node(());
+        }
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (3, 0)] {
+            cycle.add_edge(a, b, ());
+        }
+        assert!(close(&eigenvector_centrality(&cycle, 100, 1e-9).unwrap(), &[0.5; 4]));
+        assert!(close(&katz_centrality(&cycle, 0.1, 1.0, 1000, 1e-9).unwrap(), &[0.5; 4]));
+        assert!(close(&pagerank_centrality(&cycle, 0.85).unwrap().scores, &[0.25; 4]));
+        assert_eq!(pagerank_centrality(&cycle, 1.5), Err(PageRankError::InvalidDamping(1.5)));
+    }
+
+    #[test]
+    fn star_hub_leads() {
+        let mut star = Graph::<_, Undirected, ()>::new();
+        for i in 0..5 {
+            star.add_node(());
+            if i > 0 {
+                star.add_edge(0, i, ());
+            }
+        }
+        // Leading eigenvector of a star is (2, 1, 1, 1, 1) / sqrt 8.
+        let s = 8f64.sqrt();
+        let leaf = 1.0 / s;
+        let ev = eigenvector_centrality(&star, 1000, 1e-12).unwrap();
+        assert!(close(&ev, &[2.0 / s, leaf, leaf, leaf, leaf]));
+        assert!(katz_centrality(&star, 1.0, 1.0, 50, 1e-9).is_none());
+
+        // Everything flows into a sink, which then teleports.
+        let mut chain = Graph::<_, Directed, ()>::new();
+        for i in 0..3 {
+            chain.add_node(());
+            if i > 0 {
+                chain.add_edge(i - 1, i, ());
+            }
+        }
+        let run = pagerank_centrality(&chain, 0.85).unwrap();
+        assert!(run.converged);
+        let pr = run.scores;
+        assert!((pr.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(pr[0] < pr[1] && pr[1] < pr[2]);
+    }
+}
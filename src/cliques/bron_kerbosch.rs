@@ -0,0 +1,159 @@
+use crate::adjacency::{degeneracy_order, undirected_adjacency, without_loops, Adjacency};
+
+/// Lazily enumerates the maximal cliques of an undirected graph
+/// with the Bron-Kerbosch algorithm, choosing a pivot at every
+/// step and visiting the outermost level in degeneracy order
+/// (Eppstein, Löffler & Strash). Directed edges are read both
+/// ways and self loops are ignored.
+/// Time complexity:
+///   O(d * V * 3^(d/3)) where d = the degeneracy.
+pub struct MaximalCliques<'g, G: Adjacency> {
+    graph: &'g G,
+    adj: Vec<Vec<usize>>,
+    order: Vec<usize>,
+    rank: Vec<usize>,
+    top: usize,
+    stack: Vec<Frame>,
+}
+
+/// One level of the recursion: the clique grown so far, the
+/// candidates that may extend it and those already tried.
+struct Frame {
+    r: Vec<usize>,
+    p: Vec<usize>,
+    x: Vec<usize>,
+    branch: Vec<usize>,
+    i: usize,
+}
+
+impl<'g, G: Adjacency> MaximalCliques<'g, G> {
+    pub fn new(graph: &'g G) -> Self {
+        let adj = without_loops(undirected_adjacency(graph));
+        let order = degeneracy_order(&adj);
+        let mut rank = vec![0; adj.len()];
+        for (i, &v) in order.iter().enumerate() {
+            rank[v] = i;
+        }
+        Self {
+            graph,
+            adj,
+            order,
+            rank,
+            top: 0,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Pushes a level for `r`, or returns `r` itself if
+    /// it cannot be extended and is therefore maximal.
+    fn descend(&mut self, r: Vec<usize>, p: Vec<usize>, x: Vec<usize>) -> Option<Vec<usize>> {
+        if p.is_empty() {
+            return if x.is_empty() { Some(r) } else { None };
+        }
+
+        // Branch only on candidates outside the pivot's
+        // neighbourhood; the pivot covers the rest.
+        let adj = &self.adj;
+        let pivot = p
+            .iter()
+            .chain(x.iter())
+            .max_by_key(|&&u| p.iter().filter(|v| adj[u].binary_search(v).is_ok()).count())
+            .cloned()
+            .unwrap();
+        let branch = p
+            .iter()
+            .cloned()
+            .filter(|v| adj[pivot].binary_search(v).is_err())
+            .collect();
+        self.stack.push(Frame { r, p, x, branch, i: 0 });
+        None
+    }
+}
+
+impl<'g, G: Adjacency> Iterator for MaximalCliques<'g, G> {
+    type Item = Vec<G::NodeId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let found = if let Some(frame) = self.stack.last_mut() {
+                let Some(&v) = frame.branch.get(frame.i) else {
+                    self.stack.pop();
+                    continue;
+                };
+                frame.i += 1;
+                let nv = &self.adj[v];
+                let mut r = frame.r.clone();
+                r.push(v);
+                let p = frame.p.iter().cloned().filter(|u| nv.binary_search(u).is_ok()).collect();
+                let x = frame.x.iter().cloned().filter(|u| nv.binary_search(u).is_ok()).collect();
+                frame.p.retain(|&u| u != v);
+                frame.x.push(v);
+                self.descend(r, p, x)
+            } else if self.top < self.order.len() {
+                let v = self.order[self.top];
+                self.top += 1;
+                let (later, earlier) = self.adj[v]
+                    .iter()
+                    .partition(|&&u| self.rank[u] > self.rank[v]);
+                self.descend(vec![v], later, earlier)
+            } else {
+                return None;
+            };
+
+            if let Some(mut clique) = found {
+                clique.sort_unstable();
+                return Some(clique.into_iter().map(|p| self.graph.node_id(p)).collect());
+            }
+        }
+    }
+}
+
+/// Shorthand for `MaximalCliques::new`.
+pub fn maximal_cliques<G: Adjacency>(graph: &G) -> MaximalCliques<'_, G> {
+    MaximalCliques::new(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::Graph;
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn overlapping_triangles() {
+        // Two triangles sharing the edge 1 - 2, plus
+        // a pendant edge and an isolated node.
+        let mut g = mtx::Graph::<u8>::default();
+        let v: Vec<_> = (0..6).map(|i| g.add_node(i)).collect();
+        for &(a, b) in &[(0, 1), (0, 2), (1, 2), (1, 3), (2, 3), (3, 4)] {
+            g.add_edge(v[a], v[b]);
+        }
+        let mut cliques: Vec<Vec<_>> = maximal_cliques(&g).collect();
+        cliques.sort();
+        assert_eq!(
+            cliques,
+            vec![vec![v[0], v[1], v[2]], vec![v[1], v[2], v[3]], vec![v[3], v[4]], vec![v[5]]]
+        );
+    }
+
+    #[test]
+    fn lazy() {
+        // K6 minus a perfect matching has 2^3 maximal cliques,
+        // but only the first is computed here.
+        let mut g = Graph::<()>::new();
+        for _ in 0..6 {
+            g.add_node(());
+        }
+        for u in 0..6 {
+            for v in (u + 1)..6 {
+                if v != u + 3 {
+                    g.add_edge(u, v, 1);
+                }
+            }
+        }
+        let mut it = maximal_cliques(&g);
+        assert_eq!(it.next().map(|c| c.len()), Some(3));
+        assert_eq!(it.count(), 7);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::adjacency::{undirected_adjacency, without_loops, Adjacency};
+
+/// Finds a largest clique of an undirected graph by branch and
+/// bound, pruning with a greedy colouring of the candidates: a
+/// set that needs k colours cannot hold a clique of more than k
+/// nodes (Tomita's MCQ). Directed edges are read both ways.
+/// Time complexity:
+///   exponential in the worst case.
+pub fn maximum_clique<G: Adjacency>(graph: &G) -> Vec<G::NodeId> {
+    let adj = without_loops(undirected_adjacency(graph));
+    largest_clique(&adj, (0..adj.len()).collect())
+        .into_iter()
+        .map(|p| graph.node_id(p))
+        .collect()
+}
+
+/// Finds a largest set of pairwise non-adjacent nodes, as a
+/// largest clique of the complement graph. A node with a self
+/// loop is adjacent to itself, so it is never in the set.
+pub fn maximum_independent_set<G: Adjacency>(graph: &G) -> Vec<G::NodeId> {
+    let adj = undirected_adjacency(graph);
+    let n = adj.len();
+    let complement: Vec<Vec<usize>> = (0..n)
+        .map(|u| (0..n).filter(|&v| v != u && adj[u].binary_search(&v).is_err()).collect())
+        .collect();
+    let loopless = (0..n).filter(|&u| adj[u].binary_search(&u).is_err()).collect();
+    largest_clique(&complement, loopless)
+        .into_iter()
+        .map(|p| graph.node_id(p))
+        .collect()
+}
+
+/// Finds a smallest set of nodes touching every edge: whatever a
+/// maximum independent set leaves out, so it holds every node
+/// with a self loop.
+pub fn minimum_vertex_cover<G: Adjacency>(graph: &G) -> Vec<G::NodeId> {
+    let independent: Vec<usize> = maximum_independent_set(graph)
+        .into_iter()
+        .map(|id| graph.node_pos(id))
+        .collect();
+    (0..graph.node_count())
+        .filter(|p| !independent.contains(p))
+        .map(|p| graph.node_id(p))
+        .collect()
+}
+
+/// A largest clique among the nodes `p`.
+fn largest_clique(adj: &[Vec<usize>], p: Vec<usize>) -> Vec<usize> {
+    let mut best = Vec::new();
+    let mut r = Vec::new();
+    expand(adj, &mut r, p, &mut best);
+    best.sort_unstable();
+    best
+}
+
+fn expand(adj: &[Vec<usize>], r: &mut Vec<usize>, mut p: Vec<usize>, best: &mut Vec<usize>) {
+    let (order, bounds) = color_sort(adj, &p);
+    for i in (0..order.len()).rev() {
+        if r.len() + bounds[i] <= best.len() {
+            return;
+        }
+        let v = order[i];
+        r.push(v);
+        let next: Vec<usize> = p.iter().cloned().filter(|u| adj[v].binary_search(u).is_ok()).collect();
+        if next.is_empty() {
+            if r.len() > best.len() {
+                *best = r.clone();
+            }
+        } else {
+            expand(adj, r, next, best);
+        }
+        r.pop();
+        p.retain(|&u| u != v);
+    }
+}
+
+/// Greedily colours `p`, returning its nodes sorted by colour
+/// along with the number of colours used up to each of them.
+fn color_sort(adj: &[Vec<usize>], p: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let mut classes: Vec<Vec<usize>> = Vec::new();
+    for &v in p.iter() {
+        let free = classes
+            .iter()
+            .position(|class| class.iter().all(|u| adj[v].binary_search(u).is_err()));
+        match free {
+            Some(i) => classes[i].push(v),
+            None => classes.push(vec![v]),
+        }
+    }
+
+    let mut order = Vec::with_capacity(p.len());
+    let mut bounds = Vec::with_capacity(p.len());
+    for (k, class) in classes.into_iter().enumerate() {
+        for v in class {
+            order.push(v);
+            bounds.push(k + 1);
+        }
+    }
+    (order, bounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cliques::bron_kerbosch::maximal_cliques;
+    use crate::lcg::Lcg;
+    use crate::list_graph::graph::Graph;
+
+    use super::*;
+
+    #[test]
+    fn matches_enumeration() {
+        let mut rng = Lcg::new(5);
+        for _ in 0..50 {
+            let n = 1 + rng.below(14);
+            let mut g = Graph::<()>::new();
+            for _ in 0..n {
+                g.add_node(());
+            }
+            let pairs: Vec<(usize, usize)> = (0..n).flat_map(|u| ((u + 1)..n).map(move |v| (u, v))).collect();
+            for (u, v) in pairs {
+                if rng.below(5) < 3 {
+                    g.add_edge(u, v, 1);
+                }
+            }
+            let largest = maximal_cliques(&g).map(|c| c.len()).max().unwrap_or(0);
+            assert_eq!(maximum_clique(&g).len(), largest);
+        }
+    }
+
+    #[test]
+    fn cover_and_independent_set() {
+        // A 5-cycle: independent sets hold at most two
+        // nodes, so every cover needs three.
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..5).map(|_| g.add_node(())).collect();
+        for i in 0..5 {
+            g.add_edge(v[i], v[(i + 1) % 5], 1);
+        }
+        assert_eq!(maximum_clique(&g).len(), 2);
+        assert_eq!(maximum_independent_set(&g).len(), 2);
+        let cover = minimum_vertex_cover(&g);
+        assert_eq!(cover.len(), 3);
+        for i in 0..5 {
+            assert!(cover.contains(&v[i]) || cover.contains(&v[(i + 1) % 5]));
+        }
+    }
+
+    #[test]
+    fn looped_nodes_are_covered() {
+        // A self loop is only covered by its node, which
+        // therefore cannot be independent.
+        let mut g = Graph::<()>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        g.add_edge(a, b, 1);
+        g.add_edge(b, b, 1);
+        g.add_edge(c, c, 1);
+        assert_eq!(maximum_independent_set(&g), vec![a]);
+        assert_eq!(minimum_vertex_cover(&g), vec![b, c]);
+        assert_eq!(maximum_clique(&g).len(), 2);
+    }
+}
@@ -0,0 +1,4 @@
+//! Cliques and the independent sets and vertex covers
+//! that follow from them.
+pub mod bron_kerbosch;
+pub mod maximum;
@@ -0,0 +1,191 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::adjacency::{undirected_adjacency, without_loops, Adjacency};
+
+/// Colours the edges of an undirected graph so that no two edges
+/// sharing a node get the same colour, using the Misra-Gries
+/// algorithm. At most one colour more than the maximum degree is
+/// used, matching Vizing's bound. Directed edges are read both ways;
+/// parallel edges and self loops are ignored.
+///
+/// Returns every edge once as `(u, v, colour)`.
+/// Time complexity:
+///   O(V * E)
+pub fn edge_coloring<G: Adjacency>(graph: &G) -> Vec<(G::NodeId, G::NodeId, usize)> {
+    let adj = without_loops(undirected_adjacency(graph));
+    let mut mg = MisraGries::new(adj.len());
+    for (u, list) in adj.iter().enumerate() {
+        for &v in list.iter().filter(|&&v| u < v) {
+            mg.color_edge(u, v);
+        }
+    }
+
+    let mut out: Vec<((usize, usize), usize)> = mg.colors.into_iter().collect();
+    out.sort_unstable();
+    out.into_iter()
+        .map(|((u, v), c)| (graph.node_id(u), graph.node_id(v), c))
+        .collect()
+}
+
+struct MisraGries {
+    /// For every node, its coloured edges as colour -> neighbour.
+    at: Vec<BTreeMap<usize, usize>>,
+    colors: HashMap<(usize, usize), usize>,
+}
+
+impl MisraGries {
+    fn new(n: usize) -> Self {
+        Self {
+            at: vec![BTreeMap::new(); n],
+            colors: HashMap::new(),
+        }
+    }
+
+    fn is_free(&self, x: usize, c: usize) -> bool {
+        !self.at[x].contains_key(&c)
+    }
+
+    fn free_color(&self, x: usize) -> usize {
+        (0..).find(|&c| self.is_free(x, c)).unwrap()
+    }
+
+    fn color(&self, u: usize, v: usize) -> Option<usize> {
+        self.colors.get(&(u.min(v), u.max(v))).cloned()
+    }
+
+    fn set(&mut self, u: usize, v: usize, c: usize) {
+        self.at[u].insert(c, v);
+        self.at[v].insert(c, u);
+        self.colors.insert((u.min(v), u.max(v)), c);
+    }
+
+    fn unset(&mut self, u: usize, v: usize) {
+        if let Some(c) = self.colors.remove(&(u.min(v), u.max(v))) {
+            self.at[u].remove(&c);
+            self.at[v].remove(&c);
+        }
+    }
+
+    fn color_edge(&mut self, u: usize, v: usize) {
+        // A maximal fan of u: each next edge's colour
+        // is free on the previous fan node.
+        let mut fan = vec![v];
+        loop {
+            let last = *fan.last().unwrap();
+            let next = self.at[u]
+                .iter()
+                .find(|(&c, w)| !fan.contains(w) && self.is_free(last, c))
+                .map(|(_, &w)| w);
+            match next {
+                Some(w) => fan.push(w),
+                None => break,
+            }
+        }
+
+        let c = self.free_color(u);
+        let d = self.free_color(*fan.last().unwrap());
+        self.invert_path(u, c, d);
+
+        // The longest fan prefix still valid after the
+        // inversion whose end has d free.
+        let mut end = fan.len() - 1;
+        for (i, &w) in fan.iter().enumerate() {
+            let valid = (1..=i).all(|j| {
+                self.color(u, fan[j]).is_some_and(|cj| self.is_free(fan[j - 1], cj))
+            });
+            if !valid {
+                break;
+            }
+            if self.is_free(w, d) {
+                end = i;
+                break;
+            }
+        }
+
+        // Rotate the fan prefix and close it with d.
+        let shifted: Vec<usize> = (0..end).map(|i| self.color(u, fan[i + 1]).unwrap()).collect();
+        for &w in fan[1..=end].iter() {
+            self.unset(u, w);
+        }
+        for (i, &col) in shifted.iter().enumerate() {
+            self.set(u, fan[i], col);
+        }
+        self.set(u, fan[end], d);
+    }
+
+    /// Swaps colours `c` and `d` along the path from `u` that
+    /// alternates between them, starting with `d`.
+    fn invert_path(&mut self, u: usize, c: usize, d: usize) {
+        let mut path = Vec::new();
+        let (mut x, mut cur) = (u, d);
+        while let Some(&y) = self.at[x].get(&cur) {
+            path.push((x, y, cur));
+            x = y;
+            cur = if cur == d { c } else { d };
+        }
+        for &(x, y, _) in path.iter() {
+            self.unset(x, y);
+        }
+        for &(x, y, col) in path.iter() {
+            self.set(x, y, if col == d { c } else { d });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lcg::Lcg;
+    use crate::list_graph::graph::Graph;
+
+    use super::*;
+
+    fn check(g: &Graph<()>) {
+        let coloring = edge_coloring(g);
+        let max_degree = (0..g.len()).map(|v| g.edges(v).len()).max().unwrap_or(0);
+        let edge_count: usize = (0..g.len()).map(|v| g.edges(v).len()).sum::<usize>() / 2;
+        assert_eq!(coloring.len(), edge_count);
+        for (i, &(a, b, c)) in coloring.iter().enumerate() {
+            assert!(c <= max_degree);
+            for &(x, y, d) in coloring[i + 1..].iter() {
+                if a == x || a == y || b == x || b == y {
+                    assert_ne!(c, d);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn complete_graphs() {
+        for n in 2..8 {
+            let mut g = Graph::<()>::new();
+            for _ in 0..n {
+                g.add_node(());
+            }
+            for u in 0..n {
+                for v in (u + 1)..n {
+                    g.add_edge(u, v, 1);
+                }
+            }
+            check(&g);
+        }
+    }
+
+    #[test]
+    fn random_graphs() {
+        let mut rng = Lcg::new(3);
+        for _ in 0..50 {
+            let n = 2 + rng.below(12);
+            let mut g = Graph::<()>::new();
+            for _ in 0..n {
+                g.add_node(());
+            }
+            let pairs: Vec<(usize, usize)> = (0..n).flat_map(|u| ((u + 1)..n).map(move |v| (u, v))).collect();
+            for (u, v) in pairs {
+                if rng.below(2) == 0 {
+                    g.add_edge(u, v, 1);
+                }
+            }
+            check(&g);
+        }
+    }
+}
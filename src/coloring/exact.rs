@@ -0,0 +1,96 @@
+use crate::adjacency::{undirected_adjacency, without_loops, Adjacency};
+
+use super::greedy::{color_count, greedy_coloring, ColoringOrder};
+
+/// Finds a colouring with the fewest possible colours by
+/// backtracking. Starting from a DSatur colouring as the upper
+/// bound, it searches for one colour fewer until that fails.
+/// Exponential, so only meant for small graphs. Returns the colour
+/// of every node, indexed by node position.
+/// Time complexity:
+///   O(k^V) where k = the chromatic number.
+pub fn exact_coloring<G: Adjacency>(graph: &G) -> Vec<usize> {
+    let adj = without_loops(undirected_adjacency(graph));
+    let mut best = greedy_coloring(graph, ColoringOrder::DSatur);
+
+    // Colour high degree nodes first to fail early.
+    let mut order: Vec<usize> = (0..adj.len()).collect();
+    order.sort_by_key(|&v| std::cmp::Reverse(adj[v].len()));
+
+    let mut k = color_count(&best);
+    while k > 1 {
+        let mut colors = vec![usize::MAX; adj.len()];
+        if !backtrack(&adj, &order, 0, k - 1, 0, &mut colors) {
+            break;
+        }
+        best = colors;
+        k -= 1;
+    }
+
+    best
+}
+
+/// The fewest colours any proper colouring needs.
+pub fn chromatic_number<G: Adjacency>(graph: &G) -> usize {
+    color_count(&exact_coloring(graph))
+}
+
+/// Tries to colour `order[i..]` with `k` colours. New colours are
+/// only opened one at a time, which skips colour permutations.
+fn backtrack(adj: &[Vec<usize>], order: &[usize], i: usize, k: usize, used: usize, colors: &mut [usize]) -> bool {
+    let Some(&v) = order.get(i) else {
+        return true;
+    };
+
+    for c in 0..k.min(used + 1) {
+        if adj[v].iter().any(|&u| colors[u] == c) {
+            continue;
+        }
+        colors[v] = c;
+        if backtrack(adj, order, i + 1, k, used.max(c + 1), colors) {
+            return true;
+        }
+    }
+    colors[v] = usize::MAX;
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::Graph;
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn petersen() {
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..10).map(|_| g.add_node(())).collect();
+        for i in 0..5 {
+            g.add_edge(v[i], v[(i + 1) % 5], 1);
+            g.add_edge(v[i], v[i + 5], 1);
+            g.add_edge(v[i + 5], v[(i + 2) % 5 + 5], 1);
+        }
+        assert_eq!(chromatic_number(&g), 3);
+    }
+
+    #[test]
+    fn linked_triangles() {
+        // Two triangles tied together through node 3; each
+        // triangle forces three colours and three suffice.
+        let mut g = mtx::Graph::<u8>::default();
+        let v: Vec<_> = (0..7).map(|i| g.add_node(i)).collect();
+        for &(a, b) in &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 6), (6, 4), (3, 5), (0, 3)] {
+            g.add_edge(v[a], v[b]);
+        }
+        let colors = exact_coloring(&g);
+        assert_eq!(color_count(&colors), 3);
+        for a in 0..7 {
+            for b in 0..7 {
+                if g.has_edge(v[a], v[b]) {
+                    assert_ne!(colors[a], colors[b]);
+                }
+            }
+        }
+    }
+}
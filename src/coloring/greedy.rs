@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+
+use crate::adjacency::{degeneracy_order, undirected_adjacency, without_loops, Adjacency};
+
+/// The order in which `greedy_coloring` visits nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColoringOrder {
+    /// Node position order.
+    Natural,
+    /// Highest degree first (Welsh-Powell).
+    LargestFirst,
+    /// Reverse of repeatedly removing a minimum degree node
+    /// (Matula-Beck), which never needs more than degeneracy + 1
+    /// colours.
+    SmallestLast,
+    /// Most distinctly coloured neighbours first, ties broken by
+    /// degree (Brélaz's DSatur).
+    DSatur,
+}
+
+/// Colours the nodes of an undirected graph so that no edge joins
+/// two nodes of the same colour, giving each node in turn the
+/// smallest colour its neighbours do not use. Returns the colour of
+/// every node, indexed by node position; colours start at 0.
+/// Directed edges are read both ways and self loops are ignored.
+/// Time complexity:
+///   O(V^2 + E)
+pub fn greedy_coloring<G: Adjacency>(graph: &G, order: ColoringOrder) -> Vec<usize> {
+    let adj = without_loops(undirected_adjacency(graph));
+    match order {
+        ColoringOrder::Natural => color_in_order(&adj, 0..adj.len()),
+        ColoringOrder::LargestFirst => {
+            let mut nodes: Vec<usize> = (0..adj.len()).collect();
+            nodes.sort_by_key(|&v| std::cmp::Reverse(adj[v].len()));
+            color_in_order(&adj, nodes)
+        }
+        ColoringOrder::SmallestLast => {
+            let mut nodes = degeneracy_order(&adj);
+            nodes.reverse();
+            color_in_order(&adj, nodes)
+        }
+        ColoringOrder::DSatur => dsatur(&adj),
+    }
+}
+
+/// Number of colours used by a colouring.
+pub fn color_count(colors: &[usize]) -> usize {
+    colors.iter().max().map_or(0, |m| m + 1)
+}
+
+fn color_in_order(adj: &[Vec<usize>], order: impl IntoIterator<Item = usize>) -> Vec<usize> {
+    let mut colors = vec![usize::MAX; adj.len()];
+    let mut taken = vec![usize::MAX; adj.len() + 1];
+    for v in order {
+        for &u in adj[v].iter() {
+            if colors[u] != usize::MAX {
+                taken[colors[u]] = v;
+            }
+        }
+        colors[v] = (0..).find(|&c| taken[c] != v).unwrap();
+    }
+    colors
+}
+
+fn dsatur(adj: &[Vec<usize>]) -> Vec<usize> {
+    let n = adj.len();
+    let mut colors = vec![usize::MAX; n];
+    // the distinct colours around every node, at most its degree
+    let mut seen: Vec<HashSet<usize>> = vec![HashSet::new(); n];
+    for _ in 0..n {
+        let v = (0..n)
+            .filter(|&v| colors[v] == usize::MAX)
+            .max_by_key(|&v| (seen[v].len(), adj[v].len(), std::cmp::Reverse(v)))
+            .unwrap();
+        let c = (0..).find(|c| !seen[v].contains(c)).unwrap();
+        colors[v] = c;
+        for &u in adj[v].iter() {
+            seen[u].insert(c);
+        }
+    }
+    colors
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::Graph;
+
+    use super::*;
+
+    fn proper(g: &Graph<()>, colors: &[usize]) -> bool {
+        (0..g.len()).all(|v| g.neighbors(v).all(|&u| colors[u] != colors[v]))
+    }
+
+    #[test]
+    fn crown_graph() {
+        // Crown graph: u_i is adjacent to v_j for i != j. In natural
+        // interleaved order greedy needs n colours, DSatur just two.
+        // Every node has the same degree, so degree based orders
+        // are no help here.
+        let mut g = Graph::<()>::new();
+        let n = 4;
+        let nodes: Vec<usize> = (0..2 * n).map(|_| g.add_node(())).collect();
+        for i in 0..n {
+            for j in (0..n).filter(|&j| j != i) {
+                if i < j {
+                    g.add_edge(nodes[2 * i], nodes[2 * j + 1], 1);
+                    g.add_edge(nodes[2 * j], nodes[2 * i + 1], 1);
+                }
+            }
+        }
+        let natural = greedy_coloring(&g, ColoringOrder::Natural);
+        assert!(proper(&g, &natural));
+        assert_eq!(color_count(&natural), n);
+        let dsatur = greedy_coloring(&g, ColoringOrder::DSatur);
+        assert!(proper(&g, &dsatur));
+        assert_eq!(color_count(&dsatur), 2);
+        for order in [ColoringOrder::SmallestLast, ColoringOrder::LargestFirst] {
+            assert!(proper(&g, &greedy_coloring(&g, order)));
+        }
+    }
+
+    #[test]
+    fn odd_wheel() {
+        // A hub joined to a 5-cycle needs four colours.
+        let mut g = Graph::<()>::new();
+        let hub = g.add_node(());
+        let rim: Vec<usize> = (0..5).map(|_| g.add_node(())).collect();
+        for i in 0..5 {
+            g.add_edge(hub, rim[i], 1);
+            g.add_edge(rim[i], rim[(i + 1) % 5], 1);
+        }
+        let colors = greedy_coloring(&g, ColoringOrder::DSatur);
+        assert!(proper(&g, &colors));
+        assert_eq!(color_count(&colors), 4);
+    }
+}
@@ -0,0 +1,4 @@
+//! Vertex and edge colourings of undirected graphs.
+pub mod greedy;
+pub mod exact;
+pub mod edge;
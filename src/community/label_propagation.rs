@@ -0,0 +1,74 @@
+use crate::adjacency::{Adjacency, Measure};
+use crate::lcg::Lcg;
+
+use super::modularity::{relabel, Network};
+
+/// Asynchronous label propagation (Raghavan, Albert and Kumara).
+/// Every node starts with its own label; in rounds shuffled from
+/// `seed`, each node adopts the label carrying the most edge
+/// weight among its neighbours, keeping its own on a tie when it
+/// can and otherwise breaking ties at random. Stops once every
+/// node holds a heaviest label.
+/// Time complexity:
+///   O(m) per round
+pub fn label_propagation<G>(graph: &G, seed: u64) -> Vec<usize>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let net = Network::new(graph);
+    let n = net.len();
+    let mut rng = Lcg::new(seed);
+    let mut labels: Vec<usize> = (0..n).collect();
+    let mut order: Vec<usize> = (0..n).collect();
+    loop {
+        rng.shuffle(&mut order);
+        let mut stable = true;
+        for &u in &order {
+            let weights = net.community_weights(u, &labels);
+            if weights.is_empty() {
+                continue;
+            }
+            let top = weights.iter().map(|&(_, w)| w).fold(f64::NEG_INFINITY, f64::max);
+            let best: Vec<usize> = weights
+                .iter()
+                .filter(|&&(_, w)| w >= top - 1e-12)
+                .map(|&(c, _)| c)
+                .collect();
+            if !best.contains(&labels[u]) {
+                labels[u] = best[rng.below(best.len())];
+                stable = false;
+            }
+        }
+        if stable {
+            break;
+        }
+    }
+    relabel(&mut labels);
+
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn separate_cliques() {
+        let mut g = mtx::Graph::<usize, mtx::Undirected, mtx::Weighted>::default();
+        let ids: Vec<_> = (0..9).map(|i| g.add_node(i)).collect();
+        for c in 0..3 {
+            for a in 0..3 {
+                for b in a + 1..3 {
+                    g.add_edge(ids[c * 3 + a], ids[c * 3 + b], 2);
+                }
+            }
+        }
+        for seed in 0..5 {
+            let labels = label_propagation(&g, seed);
+            assert_eq!(labels, vec![0, 0, 0, 1, 1, 1, 2, 2, 2]);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::VecDeque;
+
+use crate::adjacency::{Adjacency, Measure};
+use crate::lcg::Lcg;
+
+use super::modularity::{relabel, Network};
+
+/// Leiden modularity optimisation (Traag, Waltman and van Eck).
+/// Like Louvain it alternates local moves and aggregation, but
+/// before aggregating each community is refined into
+/// well-connected subcommunities, which are then collapsed while
+/// the aggregate graph starts out from the unrefined partition.
+/// This keeps every community connected. Refinement merges
+/// greedily rather than at random, the zero temperature limit
+/// of the original method.
+/// Time complexity:
+///   O(m) per pass; in practice close to O(m log n)
+pub fn leiden<G>(graph: &G, resolution: f64, seed: u64) -> Vec<usize>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let mut net = Network::new(graph);
+    let mut rng = Lcg::new(seed);
+    let mut membership: Vec<usize> = (0..net.len()).collect();
+    let mut partition: Vec<usize> = (0..net.len()).collect();
+    loop {
+        fast_local_moving(&net, &mut partition, resolution, &mut rng);
+        if relabel(&mut partition) == net.len() {
+            break;
+        }
+        let mut refined = refine(&net, &partition, resolution, &mut rng);
+        let k = relabel(&mut refined);
+        if k == net.len() {
+            break;
+        }
+
+        let mut next = vec![0; k];
+        for (u, &r) in refined.iter().enumerate() {
+            next[r] = partition[u];
+        }
+        for m in membership.iter_mut() {
+            *m = refined[*m];
+        }
+        net = net.aggregate(&refined, k);
+        partition = next;
+    }
+
+    let mut result: Vec<usize> = membership.into_iter().map(|m| partition[m]).collect();
+    relabel(&mut result);
+    result
+}
+
+/// Queue based local moving: only nodes whose neighbourhood
+/// changed are visited again.
+fn fast_local_moving(net: &Network, partition: &mut [usize], resolution: f64, rng: &mut Lcg) {
+    let n = net.len();
+    let mut tot = vec![0.0; n];
+    for u in 0..n {
+        tot[partition[u]] += net.degree[u];
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut order);
+    let mut queued = vec![true; n];
+    let mut queue = VecDeque::from(order);
+
+    while let Some(u) = queue.pop_front() {
+        queued[u] = false;
+        let own = partition[u];
+        let k = net.degree[u];
+        tot[own] -= k;
+        let weights = net.community_weights(u, partition);
+        let gain = |c: usize, w: f64| w - resolution * tot[c] * k / net.total;
+
+        let stay = weights.iter().find(|&&(c, _)| c == own).map_or(0.0, |&(_, w)| w);
+        let mut best = (own, gain(own, stay));
+        for &(c, w) in &weights {
+            let g = gain(c, w);
+            if g > best.1 + 1e-12 {
+                best = (c, g);
+            }
+        }
+
+        tot[best.0] += k;
+        if best.0 != own {
+            partition[u] = best.0;
+            for &(v, _) in &net.adj[u] {
+                if !queued[v] && partition[v] != best.0 {
+                    queued[v] = true;
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+}
+
+/// Splits every community into subcommunities, starting from
+/// singletons and merging a well-connected singleton into the
+/// well-connected subcommunity of its community with the best
+/// positive gain.
+fn refine(net: &Network, partition: &[usize], resolution: f64, rng: &mut Lcg) -> Vec<usize> {
+    let n = net.len();
+    let k = partition.iter().max().map_or(0, |&c| c + 1);
+    let mut members = vec![Vec::new(); k];
+    let mut community_tot = vec![0.0; k];
+    for u in 0..n {
+        members[partition[u]].push(u);
+        community_tot[partition[u]] += net.degree[u];
+    }
+
+    let mut refined: Vec<usize> = (0..n).collect();
+    let mut size = vec![1usize; n];
+    let mut tot = net.degree.clone();
+    // Weight from each subcommunity to the rest of its community.
+    let mut external: Vec<f64> = (0..n)
+        .map(|u| {
+            net.adj[u]
+                .iter()
+                .filter(|&&(v, _)| partition[v] == partition[u])
+                .map(|&(_, w)| w)
+                .sum()
+        })
+        .collect();
+    let connected = |ext: f64, t: f64, c: usize| ext >= resolution * t * (community_tot[c] - t) / net.total;
+
+    for (c, nodes) in members.iter_mut().enumerate() {
+        rng.shuffle(nodes);
+        for &u in nodes.iter() {
+            if size[refined[u]] > 1 || !connected(external[u], net.degree[u], c) {
+                continue;
+            }
+            let k = net.degree[u];
+            let mut best = (refined[u], 0.0);
+            let mut link = 0.0;
+            for (r, w) in net.community_weights(u, &refined) {
+                if r == refined[u] || partition[r] != c || !connected(external[r], tot[r], c) {
+                    continue;
+                }
+                let g = w - resolution * tot[r] * k / net.total;
+                if g > best.1 + 1e-12 {
+                    best = (r, g);
+                    link = w;
+                }
+            }
+
+            let (r, _) = best;
+            if r != refined[u] {
+                size[refined[u]] -= 1;
+                refined[u] = r;
+                size[r] += 1;
+                tot[r] += k;
+                external[r] += external[u] - 2.0 * link;
+            }
+        }
+    }
+
+    refined
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::community::modularity::modularity;
+    use crate::lcg::Lcg;
+    use crate::list_graph::graph::{Graph, Undirected};
+
+    use super::*;
+
+    #[test]
+    fn finds_cliques() {
+        let mut g = Graph::<_, Undirected, f64>::new();
+        for _ in 0..20 {
+            g.add_node(());
+        }
+        for c in 0..4 {
+            let base = c * 5;
+            for a in 0..5 {
+                for b in a + 1..5 {
+                    g.add_edge(base + a, base + b, 1.0);
+                }
+            }
+            g.add_edge(base, ((c + 1) % 4) * 5 + 1, 0.5);
+        }
+        let expected: Vec<usize> = (0..20).map(|i| i / 5).collect();
+        for seed in 0..5 {
+            assert_eq!(leiden(&g, 1.0, seed), expected);
+        }
+        assert!(modularity(&g, &expected, 1.0) > 0.6);
+    }
+
+    #[test]
+    fn communities_are_connected() {
+        let mut rng = Lcg::new(3);
+        for _ in 0..20 {
+            let mut g = Graph::<_, Undirected, u32>::new();
+            for _ in 0..30 {
+                g.add_node(());
+            }
+            let pairs: Vec<(usize, usize)> = (0..30).flat_map(|a| (a + 1..30).map(move |b| (a, b))).collect();
+            for (a, b) in pairs {
+                if rng.below(10) == 0 {
+                    g.add_edge(a, b, 1 + rng.below(3) as u32);
+                }
+            }
+            let partition = leiden(&g, 1.0, rng.below(100) as u64);
+            let k = partition.iter().max().unwrap() + 1;
+            for c in 0..k {
+                let nodes: Vec<usize> = (0..30).filter(|&v| partition[v] == c).collect();
+                let mut seen = vec![nodes[0]];
+                let mut i = 0;
+                while i < seen.len() {
+                    let u = seen[i];
+                    for e in g.edges(u) {
+                        if partition[e.next] == c && !seen.contains(&e.next) {
+                            seen.push(e.next);
+                        }
+                    }
+                    i += 1;
+                }
+                assert_eq!(seen.len(), nodes.len());
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use crate::adjacency::{Adjacency, Measure};
+use crate::lcg::Lcg;
+
+use super::modularity::{relabel, Network};
+
+/// Louvain modularity optimisation: nodes greedily move to the
+/// neighbouring community with the best modularity gain until
+/// none improves, then communities are collapsed into single
+/// nodes and the process repeats on the smaller graph. Nodes
+/// are visited in an order shuffled from `seed`; a higher
+/// `resolution` favours smaller communities.
+/// Time complexity:
+///   O(m) per sweep; in practice close to O(m log n)
+pub fn louvain<G>(graph: &G, resolution: f64, seed: u64) -> Vec<usize>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let mut net = Network::new(graph);
+    let mut rng = Lcg::new(seed);
+    let mut membership: Vec<usize> = (0..net.len()).collect();
+    loop {
+        let mut partition: Vec<usize> = (0..net.len()).collect();
+        if !local_moving(&net, &mut partition, resolution, &mut rng) {
+            break;
+        }
+        let k = relabel(&mut partition);
+        for m in membership.iter_mut() {
+            *m = partition[*m];
+        }
+        net = net.aggregate(&partition, k);
+    }
+    relabel(&mut membership);
+
+    membership
+}
+
+/// Sweeps over all nodes, moving each to the community with the
+/// largest strictly positive gain, until a sweep moves nothing.
+/// Returns whether any node moved.
+pub(crate) fn local_moving(net: &Network, partition: &mut [usize], resolution: f64, rng: &mut Lcg) -> bool {
+    let n = net.len();
+    let mut tot = vec![0.0; n];
+    for u in 0..n {
+        tot[partition[u]] += net.degree[u];
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut order);
+
+    let mut moved = false;
+    loop {
+        let mut changed = false;
+        for &u in &order {
+            let own = partition[u];
+            let k = net.degree[u];
+            tot[own] -= k;
+            let weights = net.community_weights(u, partition);
+            let gain = |c: usize, w: f64| w - resolution * tot[c] * k / net.total;
+
+            let stay = weights.iter().find(|&&(c, _)| c == own).map_or(0.0, |&(_, w)| w);
+            let mut best = (own, gain(own, stay));
+            for &(c, w) in &weights {
+                let g = gain(c, w);
+                if g > best.1 + 1e-12 {
+                    best = (c, g);
+                }
+            }
+
+            tot[best.0] += k;
+            if best.0 != own {
+                partition[u] = best.0;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+        moved = true;
+    }
+
+    moved
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::community::modularity::modularity;
+    use crate::list_graph::graph::{Graph, Undirected};
+
+    use super::*;
+
+    /// Cliques of `size` nodes joined in a ring by single edges.
+    fn ring_of_cliques(cliques: usize, size: usize) -> Graph<(), Undirected, u32> {
+        let mut g = Graph::<_, Undirected, u32>::new();
+        for _ in 0..cliques * size {
+            g.add_node(());
+        }
+        for c in 0..cliques {
+            let base = c * size;
+            for a in 0..size {
+                for b in a + 1..size {
+                    g.add_edge(base + a, base + b, 1);
+                }
+            }
+            g.add_edge(base, ((c + 1) % cliques) * size + 1, 1);
+        }
+        g
+    }
+
+    #[test]
+    fn finds_cliques() {
+        let g = ring_of_cliques(6, 5);
+        let expected: Vec<usize> = (0..30).map(|i| i / 5).collect();
+        for seed in 0..5 {
+            let partition = louvain(&g, 1.0, seed);
+            assert_eq!(partition, expected);
+        }
+        assert!(modularity(&g, &expected, 1.0) > 0.7);
+    }
+
+    #[test]
+    fn resolution_controls_size() {
+        let g = ring_of_cliques(4, 4);
+        let whole = louvain(&g, 0.0, 1);
+        assert_eq!(whole, vec![0; 16]);
+        let split = louvain(&g, 10.0, 1);
+        assert!(split.iter().max().unwrap() + 1 > 4);
+    }
+}
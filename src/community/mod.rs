@@ -0,0 +1,7 @@
+//! Community detection on weighted undirected graphs. Every
+//! method returns a partition vector mapping node positions to
+//! community labels `0..k`.
+pub mod label_propagation;
+pub mod leiden;
+pub mod louvain;
+pub mod modularity;
@@ -0,0 +1,253 @@
+use std::cmp;
+
+use crate::adjacency::{multigraph_adjacency, Adjacency};
+use crate::list_graph::graph::{Graph, Undirected};
+
+const NONE: usize = usize::MAX;
+
+/// A node of a block-cut tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockCutNode<I> {
+    /// A biconnected component, as its sorted nodes.
+    Block(Vec<I>),
+    /// An articulation point shared by several blocks.
+    Cut(I),
+}
+
+/// Finds the articulation points (cut vertices) of an
+/// undirected graph, in ascending order.
+/// Time complexity:
+///   adj-matrix graph: O(V^2)
+///   adj-list graph: O(V + E)
+pub fn articulation_points<G: Adjacency>(graph: &G) -> Vec<G::NodeId> {
+    let ll = LowLink::run(multigraph_adjacency(graph));
+    (0..graph.node_count())
+        .filter(|&p| ll.cut[p])
+        .map(|p| graph.node_id(p))
+        .collect()
+}
+
+/// Finds the bridges of an undirected graph, i.e. the edges
+/// whose removal disconnects their endpoints.
+/// Time complexity:
+///   adj-matrix graph: O(V^2)
+///   adj-list graph: O(V + E)
+pub fn bridges<G: Adjacency>(graph: &G) -> Vec<(G::NodeId, G::NodeId)> {
+    let ll = LowLink::run(multigraph_adjacency(graph));
+    ll.bridges
+        .into_iter()
+        .map(|(u, v)| (graph.node_id(u), graph.node_id(v)))
+        .collect()
+}
+
+/// Splits an undirected graph into its biconnected components
+/// (blocks), each as its sorted nodes. Articulation points belong
+/// to every block they join; nodes without edges are in none.
+/// Time complexity:
+///   adj-matrix graph: O(V^2)
+///   adj-list graph: O(V + E)
+pub fn biconnected_components<G: Adjacency>(graph: &G) -> Vec<Vec<G::NodeId>> {
+    let ll = LowLink::run(multigraph_adjacency(graph));
+    ll.blocks
+        .into_iter()
+        .map(|b| b.into_iter().map(|p| graph.node_id(p)).collect())
+        .collect()
+}
+
+/// Builds the block-cut tree of an undirected graph: one node per
+/// block followed by one per articulation point, with an edge
+/// between every articulation point and the blocks containing it.
+pub fn block_cut_tree<G: Adjacency>(graph: &G) -> Graph<BlockCutNode<G::NodeId>, Undirected, ()> {
+    let ll = LowLink::run(multigraph_adjacency(graph));
+    let mut tree = Graph::<_, Undirected, ()>::new();
+    let blocks: Vec<usize> = ll
+        .blocks
+        .iter()
+        .map(|b| tree.add_node(BlockCutNode::Block(b.iter().map(|&p| graph.node_id(p)).collect())))
+        .collect();
+    for p in (0..graph.node_count()).filter(|&p| ll.cut[p]) {
+        let cut = tree.add_node(BlockCutNode::Cut(graph.node_id(p)));
+        for (i, block) in ll.blocks.iter().enumerate() {
+            if block.binary_search(&p).is_ok() {
+                tree.add_edge(cut, blocks[i], ());
+            }
+        }
+    }
+
+    tree
+}
+
+/// Dfs lowlink bookkeeping shared by the queries above, the
+/// undirected counterpart of the one in `Tarjan`.
+struct LowLink {
+    adj: Vec<Vec<usize>>,
+    time: usize,
+    disc: Vec<usize>,
+    low: Vec<usize>,
+    stack: Vec<(usize, usize)>,
+    cut: Vec<bool>,
+    bridges: Vec<(usize, usize)>,
+    blocks: Vec<Vec<usize>>,
+}
+
+impl LowLink {
+    fn run(adj: Vec<Vec<usize>>) -> Self {
+        let n = adj.len();
+        let mut ll = Self {
+            adj,
+            time: 0,
+            disc: vec![NONE; n],
+            low: vec![0; n],
+            stack: Vec::new(),
+            cut: vec![false; n],
+            bridges: Vec::new(),
+            blocks: Vec::new(),
+        };
+        for root in 0..n {
+            if ll.disc[root] == NONE {
+                ll.dfs(root, NONE);
+            }
+        }
+
+        ll
+    }
+
+    fn dfs(&mut self, at: usize, parent: usize) {
+        self.disc[at] = self.time;
+        self.low[at] = self.time;
+        self.time += 1;
+        let mut children = 0;
+        let mut parent_edge = false;
+        for i in 0..self.adj[at].len() {
+            let to = self.adj[at][i];
+            if to == at {
+                continue;
+            }
+
+            if self.disc[to] == NONE {
+                children += 1;
+                self.stack.push((at, to));
+                self.dfs(to, at);
+                self.low[at] = cmp::min(self.low[at], self.low[to]);
+
+                if self.low[to] > self.disc[at] {
+                    self.bridges.push((at, to));
+                }
+                if self.low[to] >= self.disc[at] {
+                    if parent != NONE || children > 1 {
+                        self.cut[at] = true;
+                    }
+                    self.pop_block(at, to);
+                }
+            } else if to == parent && !parent_edge {
+                // Skip the tree edge we came in over, but only once:
+                // a parallel copy of it is a back edge.
+                parent_edge = true;
+            } else if self.disc[to] < self.disc[at] {
+                self.low[at] = cmp::min(self.low[at], self.disc[to]);
+                self.stack.push((at, to));
+            }
+        }
+    }
+
+    /// Pops the edges of the block closed by the tree edge `at`-`to`.
+    fn pop_block(&mut self, at: usize, to: usize) {
+        let mut block = Vec::new();
+        while let Some((u, v)) = self.stack.pop() {
+            block.push(u);
+            block.push(v);
+            if (u, v) == (at, to) {
+                break;
+            }
+        }
+        block.sort_unstable();
+        block.dedup();
+        self.blocks.push(block);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    /// Two triangles sharing node 2, with a tail 3 - 5.
+    ///
+    ///   0       3
+    ///   | \   / |
+    ///   |  (2)  |
+    ///   | /   \ |
+    ///   1       4 --- 5
+    fn bowtie() -> Graph<()> {
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..6).map(|_| g.add_node(())).collect();
+        g.add_edge(v[0], v[1], 1);
+        g.add_edge(v[1], v[2], 1);
+        g.add_edge(v[2], v[0], 1);
+        g.add_edge(v[2], v[3], 1);
+        g.add_edge(v[3], v[4], 1);
+        g.add_edge(v[4], v[2], 1);
+        g.add_edge(v[4], v[5], 1);
+        g
+    }
+
+    #[test]
+    fn cut_vertices_and_bridges() {
+        let g = bowtie();
+        assert_eq!(articulation_points(&g), vec![2, 4]);
+        assert_eq!(bridges(&g), vec![(4, 5)]);
+    }
+
+    #[test]
+    fn blocks() {
+        let g = bowtie();
+        let mut blocks = biconnected_components(&g);
+        blocks.sort();
+        assert_eq!(blocks, vec![vec![0, 1, 2], vec![2, 3, 4], vec![4, 5]]);
+    }
+
+    #[test]
+    fn tree_shape() {
+        let g = bowtie();
+        let tree = block_cut_tree(&g);
+        // three blocks and two cut vertices
+        assert_eq!(tree.len(), 5);
+        let cuts: Vec<usize> = (0..tree.len())
+            .filter(|&i| matches!(tree[i].data, BlockCutNode::Cut(_)))
+            .collect();
+        assert_eq!(cuts.len(), 2);
+        assert_eq!(tree.edges(cuts[0]).len(), 2);
+        assert_eq!(tree.edges(cuts[1]).len(), 2);
+    }
+
+    #[test]
+    fn matrix_path() {
+        // a - b - c: every edge is a bridge
+        // and the middle node is a cut vertex.
+        let mut g = mtx::Graph::<char>::default();
+        let a = g.add_node('a');
+        let b = g.add_node('b');
+        let c = g.add_node('c');
+        g.add_edge(a, b);
+        g.add_edge(b, c);
+        assert_eq!(articulation_points(&g), vec![b]);
+        assert_eq!(bridges(&g).len(), 2);
+    }
+
+    #[test]
+    fn parallel_edges() {
+        // 0 = 1 - 2: the doubled edge is no bridge and, with
+        // node 0, forms a block of its own.
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..3).map(|_| g.add_node(())).collect();
+        g.add_edge(v[0], v[1], 1);
+        g.add_edge(v[0], v[1], 1);
+        g.add_edge(v[1], v[2], 1);
+        assert_eq!(bridges(&g), vec![(1, 2)]);
+        assert_eq!(articulation_points(&g), vec![1]);
+        let mut blocks = biconnected_components(&g);
+        blocks.sort();
+        assert_eq!(blocks, vec![vec![0, 1], vec![1, 2]]);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::adjacency::{undirected_adjacency, Adjacency};
+
+/// A partition of the nodes of a graph into components.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Components<I> {
+    /// The component of every node, indexed by node position.
+    /// Components are numbered in order of their smallest node.
+    pub labels: Vec<usize>,
+    /// The nodes of every component, in ascending order.
+    pub groups: Vec<Vec<I>>,
+}
+
+impl<I> Components<I> {
+    /// Number of components.
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+}
+
+/// Labels the connected components of an undirected graph with
+/// repeated dfs. Directed edges are followed both ways, which
+/// makes this the same as `weakly_connected_components`.
+/// Time complexity:
+///   adj-matrix graph: O(V^2)
+///   adj-list graph: O(V + E)
+pub fn connected_components<G: Adjacency>(graph: &G) -> Components<G::NodeId> {
+    let adj = undirected_adjacency(graph);
+    let labels = label(&adj);
+    let count = labels.iter().max().map_or(0, |m| m + 1);
+    let mut groups = vec![Vec::new(); count];
+    for (pos, &l) in labels.iter().enumerate() {
+        groups[l].push(graph.node_id(pos));
+    }
+
+    Components { labels, groups }
+}
+
+/// Labels the weakly connected components of a directed graph,
+/// i.e. the components left once edge directions are ignored.
+pub fn weakly_connected_components<G: Adjacency>(graph: &G) -> Components<G::NodeId> {
+    connected_components(graph)
+}
+
+/// Whether every node can reach every other one
+/// when edge directions are ignored.
+pub fn is_connected<G: Adjacency>(graph: &G) -> bool {
+    connected_components(graph).len() <= 1
+}
+
+pub(crate) fn label(adj: &[Vec<usize>]) -> Vec<usize> {
+    let mut labels = vec![usize::MAX; adj.len()];
+    let mut next = 0;
+    for root in 0..adj.len() {
+        if labels[root] != usize::MAX {
+            continue;
+        }
+
+        labels[root] = next;
+        let mut frontier = vec![root];
+        while let Some(at) = frontier.pop() {
+            for &neighbor in adj[at].iter() {
+                if labels[neighbor] == usize::MAX {
+                    labels[neighbor] = next;
+                    frontier.push(neighbor);
+                }
+            }
+        }
+        next += 1;
+    }
+
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn undirected_list() {
+        let mut g = Graph::<()>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        let d = g.add_node(());
+        let e = g.add_node(());
+        g.add_edge(a, c, 1);
+        g.add_edge(b, d, 1);
+        g.add_edge(d, e, 1);
+        let cc = connected_components(&g);
+        assert_eq!(cc.labels, vec![0, 1, 0, 1, 1]);
+        assert_eq!(cc.groups, vec![vec![a, c], vec![b, d, e]]);
+        assert!(!is_connected(&g));
+    }
+
+    #[test]
+    fn undirected_matrix() {
+        let mut g = mtx::Graph::<char>::default();
+        let a = g.add_node('a');
+        let b = g.add_node('b');
+        let c = g.add_node('c');
+        g.add_edge(a, b);
+        let cc = connected_components(&g);
+        assert_eq!(cc.groups, vec![vec![a, b], vec![c]]);
+        assert_eq!(cc.labels[usize::from(c)], 1);
+    }
+
+    #[test]
+    fn weakly_connected() {
+        // a -> b <- c is one weak component,
+        // but three strong ones.
+        let mut g = Graph::<(), Directed>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        let d = g.add_node(());
+        g.add_edge(a, b, 1);
+        g.add_edge(c, b, 1);
+        let cc = weakly_connected_components(&g);
+        assert_eq!(cc.len(), 2);
+        assert_eq!(cc.groups[1], vec![d]);
+    }
+}
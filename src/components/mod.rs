@@ -0,0 +1,3 @@
+//! Partitions of a graph into connected pieces.
+pub mod connected;
+pub mod biconnected;
@@ -0,0 +1,143 @@
+use crate::adjacency::{bucket_cores, undirected_adjacency, without_loops, Adjacency};
+use crate::list_graph::graph::{Graph, Undirected};
+
+/// Core numbers of a graph: node `v` has core number `k` when it
+/// belongs to the largest subgraph in which every node has at
+/// least `k` neighbours, but not to a larger such k.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoreDecomposition<I> {
+    /// Core number by node position.
+    pub core: Vec<usize>,
+    /// The largest core number.
+    pub degeneracy: usize,
+    /// Nodes in the order they were peeled off, each having at
+    /// most `degeneracy` neighbours later in the order.
+    pub order: Vec<I>,
+}
+
+/// Computes the core decomposition with the bucket algorithm of
+/// Batagelj and Zaversnik, which repeatedly removes a node of
+/// minimum remaining degree. Directed edges are read both ways;
+/// self loops and parallel edges are ignored.
+/// Time complexity:
+///   O(n + m)
+pub fn core_decomposition<G: Adjacency>(graph: &G) -> CoreDecomposition<G::NodeId> {
+    let adj = without_loops(undirected_adjacency(graph));
+    let (core, order) = bucket_cores(&adj);
+    CoreDecomposition {
+        degeneracy: core.iter().copied().max().unwrap_or(0),
+        core,
+        order: order.into_iter().map(|p| graph.node_id(p)).collect(),
+    }
+}
+
+/// Core number of every node, by position.
+pub fn core_numbers<G: Adjacency>(graph: &G) -> Vec<usize> {
+    core_decomposition(graph).core
+}
+
+/// The smallest `d` such that every subgraph has a node of
+/// degree at most `d`.
+pub fn degeneracy<G: Adjacency>(graph: &G) -> usize {
+    core_decomposition(graph).degeneracy
+}
+
+/// Nodes in smallest-last order: each has at most `degeneracy`
+/// neighbours after it.
+pub fn degeneracy_ordering<G: Adjacency>(graph: &G) -> Vec<G::NodeId> {
+    core_decomposition(graph).order
+}
+
+/// The k-core of an undirected graph: the subgraph induced by the
+/// nodes of core number at least `k`, as a new graph holding
+/// copies of their data and edges. Nodes keep their relative
+/// order; self loops count for nothing when peeling but are kept.
+/// Time complexity:
+///   O(n + m)
+pub fn k_core<V, E>(graph: &Graph<V, Undirected, E>, k: usize) -> Graph<V, Undirected, E>
+where
+    V: Clone,
+    E: Copy,
+{
+    let core = core_numbers(graph);
+    let mut index = vec![None; graph.len()];
+    let mut sub = Graph::<V, Undirected, E>::new();
+    for (v, _) in core.iter().enumerate().filter(|&(_, &c)| c >= k) {
+        index[v] = Some(sub.add_node(graph[v].data.clone()));
+    }
+
+    for u in 0..graph.len() {
+        let a = match index[u] {
+            Some(a) => a,
+            None => continue,
+        };
+        // Undirected edges are stored at both ends, loops twice
+        // at the same node.
+        let mut loops = 0;
+        for e in graph.edges(u) {
+            match index[e.next] {
+                Some(b) if e.next > u => sub.add_edge(a, b, e.weight),
+                Some(_) if e.next == u => {
+                    loops += 1;
+                    if loops % 2 == 0 {
+                        sub.add_edge(a, a, e.weight);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    sub
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn clique_with_tail() {
+        // K4 on 0..4 with a path 3 - 4 - 5 hanging off.
+        let mut g = mtx::Graph::<u8, mtx::Undirected, mtx::Unweighted>::default();
+        let ids: Vec<_> = (0..6).map(|i| g.add_node(i)).collect();
+        for (a, b) in [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3), (3, 4), (4, 5)] {
+            g.add_edge(ids[a], ids[b]);
+        }
+        let cores = core_decomposition(&g);
+        assert_eq!(cores.core, vec![3, 3, 3, 3, 1, 1]);
+        assert_eq!(cores.degeneracy, 3);
+        // Every node has at most 3 neighbours later in the order.
+        let rank: Vec<usize> = (0..6)
+            .map(|v| cores.order.iter().position(|&id| id == ids[v]).unwrap())
+            .collect();
+        for v in 0..6 {
+            let later = g.out_neighbors(v).into_iter().filter(|&u| rank[u] > rank[v]).count();
+            assert!(later <= 3);
+        }
+    }
+
+    #[test]
+    fn extract_core() {
+        let mut g = Graph::<&str, Undirected, u32>::new();
+        for name in ["a", "tail", "b", "c"] {
+            g.add_node(name);
+        }
+        for (x, y, w) in [(0, 2, 1), (2, 3, 2), (3, 0, 3), (0, 1, 4), (2, 2, 5)] {
+            g.add_edge(x, y, w);
+        }
+        let core = k_core(&g, 2);
+        let names: Vec<&str> = core.nodes().iter().map(|n| n.data).collect();
+        assert_eq!(names, vec!["a", "b", "c"]);
+        let mut edges: Vec<(usize, usize, u32)> = (0..3)
+            .flat_map(|u| core.edges(u).iter().map(move |e| (u, e.next, e.weight)))
+            .collect();
+        edges.sort_unstable();
+        assert_eq!(
+            edges,
+            vec![(0, 1, 1), (0, 2, 3), (1, 0, 1), (1, 1, 5), (1, 1, 5), (1, 2, 2), (2, 0, 3), (2, 1, 2)]
+        );
+        assert_eq!(k_core(&g, 3).len(), 0);
+    }
+}
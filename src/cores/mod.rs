@@ -0,0 +1,2 @@
+//! k-core decomposition and degeneracy orderings.
+pub mod batagelj_zaversnik;
@@ -0,0 +1,4 @@
+//! Minimum cuts, both between a source and a sink and
+//! over the whole graph.
+pub mod st;
+pub mod stoer_wagner;
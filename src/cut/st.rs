@@ -0,0 +1,104 @@
+use std::collections::VecDeque;
+
+use crate::adjacency::{Adjacency, Measure};
+use crate::flow::{dinic, network::FlowNetwork};
+
+/// A minimum cut separating a source from a sink.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StCut<I> {
+    /// Total capacity of the cut edges, equal to the max flow.
+    pub value: i64,
+    /// Nodes still reachable from the source in the residual
+    /// network once the flow is maximal.
+    pub source_side: Vec<I>,
+    /// Edges leaving the source side, as `(from, to)`.
+    pub edges: Vec<(I, I)>,
+}
+
+/// Computes a minimum `source`-`sink` cut by saturating the
+/// graph with Dinic's algorithm and collecting the nodes the
+/// source can still reach through residual arcs.
+/// Edge weights are read as capacities.
+/// Time complexity:
+///   O(V^2 * E)
+pub fn min_st_cut<G>(graph: &G, source: G::NodeId, sink: G::NodeId) -> StCut<G::NodeId>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let mut net = FlowNetwork::new(graph);
+    let (s, t) = (graph.node_pos(source), graph.node_pos(sink));
+    let value = dinic::run(&mut net, s, t);
+
+    let mut reachable = vec![false; net.len()];
+    reachable[s] = true;
+    let mut frontier = VecDeque::new();
+    frontier.push_back(s);
+    while let Some(at) = frontier.pop_front() {
+        for &a in net.adj[at].iter() {
+            let arc = &net.arcs[a];
+            if arc.residual() > 0 && !reachable[arc.to] {
+                reachable[arc.to] = true;
+                frontier.push_back(arc.to);
+            }
+        }
+    }
+
+    let source_side = (0..net.len())
+        .filter(|&p| reachable[p])
+        .map(|p| net.ids[p])
+        .collect();
+    let edges = net
+        .arcs
+        .iter()
+        .step_by(2)
+        .filter(|a| reachable[a.from] && !reachable[a.to])
+        .map(|a| (net.ids[a.from], net.ids[a.to]))
+        .collect();
+
+    StCut {
+        value,
+        source_side,
+        edges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn bottleneck() {
+        // (s) -5-> (a) -1-> (b) -5-> (t)
+        let mut g = Graph::<(), Directed>::new();
+        let s = g.add_node(());
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let t = g.add_node(());
+        g.add_edge(s, a, 5);
+        g.add_edge(a, b, 1);
+        g.add_edge(b, t, 5);
+        let cut = min_st_cut(&g, s, t);
+        assert_eq!(cut.value, 1);
+        assert_eq!(cut.source_side, vec![s, a]);
+        assert_eq!(cut.edges, vec![(a, b)]);
+    }
+
+    #[test]
+    fn matrix_indices() {
+        let mut g = mtx::Graph::<u8, mtx::Directed, mtx::Weighted>::default();
+        let s = g.add_node(0);
+        let a = g.add_node(1);
+        let t = g.add_node(2);
+        g.add_edge(s, a, 1);
+        g.add_edge(a, t, 3);
+        g.add_edge(s, t, 2);
+        let cut = min_st_cut(&g, s, t);
+        assert_eq!(cut.value, 3);
+        assert_eq!(cut.source_side, vec![s]);
+        assert_eq!(cut.edges, vec![(s, a), (s, t)]);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::adjacency::{Adjacency, Measure};
+
+/// A minimum cut over all possible bipartitions of a graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalCut<I> {
+    /// Total weight of the edges crossing the cut.
+    pub value: i64,
+    /// The nodes on one side of the cut; every other
+    /// node is on the other side.
+    pub partition: Vec<I>,
+}
+
+/// Computes the global minimum cut of an undirected weighted
+/// graph using the Stoer-Wagner algorithm. Directed graphs
+/// are read as if every edge went both ways.
+///
+/// Returns `None` for graphs with fewer than two nodes.
+/// Time complexity:
+///   O(V^3)
+pub fn stoer_wagner<G>(graph: &G) -> Option<GlobalCut<G::NodeId>>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let n = graph.node_count();
+    if n < 2 {
+        return None;
+    }
+
+    let mut w = vec![vec![0i64; n]; n];
+    let edges = (0..n).flat_map(|u| graph.out_edges(u).into_iter().map(move |(v, x)| (u, v, x)));
+    for (u, v, weight) in edges {
+        if u == v {
+            continue;
+        }
+        w[u][v] += weight.to_i64();
+        if graph.is_directed() {
+            w[v][u] += weight.to_i64();
+        }
+    }
+
+    // Every merged super node remembers which
+    // original nodes it is made of.
+    let mut members: Vec<Vec<usize>> = (0..n).map(|v| vec![v]).collect();
+    let mut alive: Vec<usize> = (0..n).collect();
+    let mut best: Option<(i64, Vec<usize>)> = None;
+
+    while alive.len() > 1 {
+        // Maximum adjacency search.
+        let mut key = vec![0i64; n];
+        let mut added = vec![false; n];
+        let mut prev = alive[0];
+        let mut last = alive[0];
+        for i in 0..alive.len() {
+            let next = *alive
+                .iter()
+                .filter(|&&v| !added[v])
+                .max_by_key(|&&v| (key[v], std::cmp::Reverse(v)))
+                .unwrap();
+            added[next] = true;
+            if i == alive.len() - 1 {
+                if best.as_ref().is_none_or(|(value, _)| key[next] < *value) {
+                    best = Some((key[next], members[next].clone()));
+                }
+            } else {
+                for &v in alive.iter() {
+                    key[v] += w[next][v];
+                }
+            }
+            prev = last;
+            last = next;
+        }
+
+        // Merge the last node into the one before it.
+        let (s, t) = (prev, last);
+        let moved = std::mem::take(&mut members[t]);
+        members[s].extend(moved);
+        for &v in alive.iter() {
+            w[s][v] += w[t][v];
+            w[v][s] = w[s][v];
+        }
+        w[s][s] = 0;
+        alive.retain(|&v| v != t);
+    }
+
+    best.map(|(value, mut part)| {
+        part.sort_unstable();
+        GlobalCut {
+            value,
+            partition: part.into_iter().map(|p| graph.node_id(p)).collect(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::Graph;
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn two_triangles() {
+        // Two heavy triangles joined by a single light edge.
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..6).map(|_| g.add_node(())).collect();
+        g.add_edge(v[0], v[1], 3);
+        g.add_edge(v[1], v[2], 3);
+        g.add_edge(v[2], v[0], 3);
+        g.add_edge(v[3], v[4], 3);
+        g.add_edge(v[4], v[5], 3);
+        g.add_edge(v[5], v[3], 3);
+        g.add_edge(v[2], v[3], 1);
+        let cut = stoer_wagner(&g).unwrap();
+        assert_eq!(cut.value, 1);
+        let mut side = cut.partition.clone();
+        if side.contains(&v[3]) {
+            side = (0..6).filter(|x| !cut.partition.contains(x)).collect();
+        }
+        assert_eq!(side, vec![v[0], v[1], v[2]]);
+    }
+
+    #[test]
+    fn wikipedia_example() {
+        let mut g = mtx::Graph::<u8, mtx::Undirected, mtx::Weighted>::default();
+        let n: Vec<_> = (1..=8).map(|i| g.add_node(i)).collect();
+        for &(a, b, w) in &[
+            (1, 2, 2), (1, 5, 3), (2, 3, 3), (2, 5, 2), (2, 6, 2),
+            (3, 4, 4), (3, 7, 2), (4, 7, 2), (4, 8, 2), (5, 6, 3),
+            (6, 7, 1), (7, 8, 3),
+        ] {
+            g.add_edge(n[a - 1], n[b - 1], w);
+        }
+        let cut = stoer_wagner(&g).unwrap();
+        assert_eq!(cut.value, 4);
+    }
+
+    #[test]
+    fn too_small() {
+        let mut g = Graph::<()>::new();
+        g.add_node(());
+        assert!(stoer_wagner(&g).is_none());
+    }
+}
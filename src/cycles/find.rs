@@ -0,0 +1,178 @@
+use crate::adjacency::{multigraph_adjacency, Adjacency};
+
+const NONE: usize = usize::MAX;
+
+/// Finds any one cycle, returned as the sequence of its nodes
+/// (the last one links back to the first). Directed graphs must
+/// follow edge directions; undirected ones need a cycle that does
+/// not reuse an edge, so on the list backend parallel edges make
+/// a cycle of two nodes. Self loops count as cycles of one node.
+/// Time complexity:
+///   adj-matrix graph: O(V^2)
+///   adj-list graph: O(V + E)
+pub fn find_cycle<G: Adjacency>(graph: &G) -> Option<Vec<G::NodeId>> {
+    let cycle = if graph.is_directed() {
+        let succs: Vec<Vec<usize>> = (0..graph.node_count()).map(|p| graph.out_neighbors(p)).collect();
+        directed(&succs)
+    } else {
+        undirected(&multigraph_adjacency(graph))
+    };
+    cycle.map(|c| c.into_iter().map(|p| graph.node_id(p)).collect())
+}
+
+/// Whether the graph has no cycle, i.e. is a DAG when
+/// directed or a forest when undirected.
+pub fn is_acyclic<G: Adjacency>(graph: &G) -> bool {
+    find_cycle(graph).is_none()
+}
+
+/// Colour based dfs: reaching a node still on the
+/// stack closes a cycle.
+fn directed(succs: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let n = succs.len();
+    let mut parent = vec![NONE; n];
+    let mut state = vec![0u8; n]; // 0 new, 1 on stack, 2 done
+    for root in 0..n {
+        if state[root] != 0 {
+            continue;
+        }
+        let mut stack = vec![(root, 0)];
+        state[root] = 1;
+        while let Some((at, i)) = stack.pop() {
+            let Some(&next) = succs[at].get(i) else {
+                state[at] = 2;
+                continue;
+            };
+            stack.push((at, i + 1));
+            match state[next] {
+                0 => {
+                    parent[next] = at;
+                    state[next] = 1;
+                    stack.push((next, 0));
+                }
+                1 => return Some(unwind(&parent, at, next)),
+                _ => {}
+            }
+        }
+    }
+
+    None
+}
+
+/// Dfs remembering the tree parent: any other visited
+/// neighbour closes a cycle, as does a second edge to the parent.
+fn undirected(adj: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let n = adj.len();
+    let mut parent = vec![NONE; n];
+    let mut visited = vec![false; n];
+    for root in 0..n {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut stack = vec![root];
+        while let Some(at) = stack.pop() {
+            let mut parent_edge = false;
+            for &next in adj[at].iter() {
+                if next == at {
+                    return Some(vec![at]);
+                }
+                if next == parent[at] && !parent_edge {
+                    parent_edge = true;
+                    continue;
+                }
+                if visited[next] {
+                    return Some(join(&parent, at, next));
+                }
+                visited[next] = true;
+                parent[next] = at;
+                stack.push(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Follows tree parents from `at` back up to `to`.
+fn unwind(parent: &[usize], mut at: usize, to: usize) -> Vec<usize> {
+    let mut cycle = vec![at];
+    while at != to {
+        at = parent[at];
+        cycle.push(at);
+    }
+    cycle.reverse();
+    cycle
+}
+
+/// Joins the tree paths of `a` and `b` at their common ancestor.
+fn join(parent: &[usize], a: usize, b: usize) -> Vec<usize> {
+    let mut up_a = vec![a];
+    while parent[*up_a.last().unwrap()] != NONE {
+        up_a.push(parent[*up_a.last().unwrap()]);
+    }
+    let mut up_b = vec![b];
+    while !up_a.contains(up_b.last().unwrap()) {
+        up_b.push(parent[*up_b.last().unwrap()]);
+    }
+    let meet = *up_b.last().unwrap();
+    let mut cycle: Vec<usize> = up_a.into_iter().take_while(|&v| v != meet).collect();
+    cycle.push(meet);
+    cycle.extend(up_b.into_iter().rev().skip(1));
+    cycle
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn directed_cycle() {
+        let mut g = Graph::<(), Directed>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        let d = g.add_node(());
+        g.add_edge(a, b, 1);
+        g.add_edge(b, c, 1);
+        g.add_edge(c, d, 1);
+        g.add_edge(d, b, 1);
+        assert_eq!(find_cycle(&g), Some(vec![b, c, d]));
+
+        let mut dag = Graph::<(), Directed>::new();
+        let a = dag.add_node(());
+        let b = dag.add_node(());
+        let c = dag.add_node(());
+        dag.add_edge(a, b, 1);
+        dag.add_edge(a, c, 1);
+        dag.add_edge(b, c, 1);
+        assert!(is_acyclic(&dag));
+    }
+
+    #[test]
+    fn undirected_cycle() {
+        let mut g = mtx::Graph::<u8>::default();
+        let v: Vec<_> = (0..5).map(|i| g.add_node(i)).collect();
+        g.add_edge(v[0], v[1]);
+        g.add_edge(v[1], v[2]);
+        g.add_edge(v[2], v[3]);
+        g.add_edge(v[3], v[1]);
+        g.add_edge(v[3], v[4]);
+        let cycle = find_cycle(&g).unwrap();
+        assert_eq!(cycle.len(), 3);
+        for i in 0..cycle.len() {
+            assert!(g.has_edge(cycle[i], cycle[(i + 1) % cycle.len()]));
+        }
+
+        let mut tree = Graph::<()>::new();
+        let a = tree.add_node(());
+        let b = tree.add_node(());
+        tree.add_edge(a, b, 1);
+        assert!(is_acyclic(&tree));
+        tree.add_edge(b, a, 1);
+        assert_eq!(find_cycle(&tree), Some(vec![a, b]));
+    }
+}
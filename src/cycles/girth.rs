@@ -0,0 +1,171 @@
+use std::collections::BinaryHeap;
+
+use crate::adjacency::{Adjacency, Measure};
+use crate::queue::QueueNode;
+
+const NONE: usize = usize::MAX;
+
+/// Finds a cycle of minimum total weight, returning its weight and
+/// its nodes in order. Directed graphs close a cycle over each edge
+/// back into a Dijkstra source; undirected graphs look for the
+/// shortest detour around every edge. Parallel undirected edges,
+/// as on the list backend, form a cycle of two nodes. Weights
+/// must be non-negative.
+/// Time complexity:
+///   O(V * E log V) directed, O(E^2 log V) undirected
+pub fn minimum_weight_cycle<G>(graph: &G) -> Option<(i64, Vec<G::NodeId>)>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let n = graph.node_count();
+    let adj: Vec<Vec<(usize, i64)>> = (0..n)
+        .map(|p| graph.out_edges(p).into_iter().map(|(v, w)| (v, w.to_i64())).collect())
+        .collect();
+    shortest_cycle(&adj, graph.is_directed())
+        .map(|(w, c)| (w, c.into_iter().map(|p| graph.node_id(p)).collect()))
+}
+
+/// The number of edges in the shortest cycle, ignoring weights.
+pub fn girth<G: Adjacency>(graph: &G) -> Option<usize> {
+    let n = graph.node_count();
+    let adj: Vec<Vec<(usize, i64)>> = (0..n)
+        .map(|p| graph.out_neighbors(p).into_iter().map(|v| (v, 1)).collect())
+        .collect();
+    shortest_cycle(&adj, graph.is_directed()).map(|(_, c)| c.len())
+}
+
+fn shortest_cycle(adj: &[Vec<(usize, i64)>], directed: bool) -> Option<(i64, Vec<usize>)> {
+    let mut best: Option<(i64, Vec<usize>)> = None;
+    let mut consider = |w: i64, cycle: Vec<usize>| {
+        if best.as_ref().is_none_or(|(b, _)| w < *b) {
+            best = Some((w, cycle));
+        }
+    };
+
+    for (u, edges) in adj.iter().enumerate() {
+        for &(v, w) in edges.iter().filter(|(v, _)| *v == u) {
+            consider(w, vec![v]);
+        }
+    }
+
+    if directed {
+        for s in 0..adj.len() {
+            let (dist, prev) = dijkstra(adj, s, None);
+            for (u, edges) in adj.iter().enumerate() {
+                for &(_, w) in edges.iter().filter(|(v, _)| *v == s && u != s) {
+                    if let Some(d) = dist[u] {
+                        consider(d + w, path(&prev, s, u));
+                    }
+                }
+            }
+        }
+    } else {
+        for (u, edges) in adj.iter().enumerate() {
+            // The two lightest copies of a parallel edge.
+            let mut out: Vec<(usize, i64)> = edges.iter().copied().filter(|&(v, _)| u < v).collect();
+            out.sort_unstable();
+            for pair in out.windows(2).filter(|p| p[0].0 == p[1].0) {
+                consider(pair[0].1 + pair[1].1, vec![u, pair[0].0]);
+            }
+            // Longer cycles detour around every copy of an edge.
+            for &(v, w) in edges.iter().filter(|(v, _)| u < *v) {
+                let (dist, prev) = dijkstra(adj, u, Some((u, v)));
+                if let Some(d) = dist[v] {
+                    consider(d + w, path(&prev, u, v));
+                }
+            }
+        }
+    }
+
+    best
+}
+
+/// Dijkstra from `s`, optionally ignoring the undirected
+/// edge `skip`. Returns distances and predecessors.
+fn dijkstra(adj: &[Vec<(usize, i64)>], s: usize, skip: Option<(usize, usize)>) -> (Vec<Option<i64>>, Vec<usize>) {
+    let mut dist: Vec<Option<i64>> = vec![None; adj.len()];
+    let mut prev = vec![NONE; adj.len()];
+    let mut frontier = BinaryHeap::<QueueNode<usize, i64>>::new();
+    dist[s] = Some(0);
+    frontier.push(QueueNode::new(s, 0));
+    while let Some(current) = frontier.pop() {
+        if dist[current.idx].is_some_and(|d| d < current.weight) {
+            continue;
+        }
+        for &(next, w) in adj[current.idx].iter() {
+            if let Some((a, b)) = skip {
+                if (current.idx, next) == (a, b) || (current.idx, next) == (b, a) {
+                    continue;
+                }
+            }
+            let new_cost = current.weight + w;
+            if dist[next].is_none_or(|d| new_cost < d) {
+                dist[next] = Some(new_cost);
+                prev[next] = current.idx;
+                frontier.push(QueueNode::new(next, new_cost));
+            }
+        }
+    }
+
+    (dist, prev)
+}
+
+fn path(prev: &[usize], s: usize, mut to: usize) -> Vec<usize> {
+    let mut out = vec![to];
+    while to != s {
+        to = prev[to];
+        out.push(to);
+    }
+    out.reverse();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph};
+
+    use super::*;
+
+    #[test]
+    fn directed_min_weight() {
+        // a -> b -> a costs 10, b -> c -> d -> b costs 3
+        let mut g = Graph::<(), Directed>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        let d = g.add_node(());
+        g.add_edge(a, b, 5);
+        g.add_edge(b, a, 5);
+        g.add_edge(b, c, 1);
+        g.add_edge(c, d, 1);
+        g.add_edge(d, b, 1);
+        let (w, cycle) = minimum_weight_cycle(&g).unwrap();
+        assert_eq!(w, 3);
+        assert_eq!(cycle.len(), 3);
+        assert_eq!(girth(&g), Some(2));
+    }
+
+    #[test]
+    fn undirected_girth() {
+        // A square with one diagonal has girth 3; a single
+        // edge has no cycle at all, but a doubled one does.
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..4).map(|_| g.add_node(())).collect();
+        for i in 0..4 {
+            g.add_edge(v[i], v[(i + 1) % 4], 1);
+        }
+        assert_eq!(girth(&g), Some(4));
+        g.add_edge(v[0], v[2], 1);
+        assert_eq!(girth(&g), Some(3));
+
+        let mut line = Graph::<()>::new();
+        let a = line.add_node(());
+        let b = line.add_node(());
+        line.add_edge(a, b, 1);
+        assert_eq!(girth(&line), None);
+        line.add_edge(b, a, 4);
+        assert_eq!(girth(&line), Some(2));
+        assert_eq!(minimum_weight_cycle(&line), Some((5, vec![a, b])));
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::VecDeque;
+
+use crate::adjacency::Adjacency;
+
+/// Bounds on an elementary cycle enumeration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CycleLimits {
+    /// Stop after this many cycles.
+    pub max_count: Option<usize>,
+    /// Skip cycles with more nodes than this.
+    pub max_len: Option<usize>,
+}
+
+/// Enumerates the elementary cycles of a directed graph using
+/// Johnson's algorithm. Each cycle is the sequence of its nodes
+/// starting from its smallest one. On undirected graphs every
+/// cycle is listed once, in one direction, and an edge is only a
+/// cycle of two nodes when doubled, as the list backend allows.
+///
+/// With a `max_len` the blocking that makes Johnson's algorithm
+/// output sensitive is unsound, so plain backtracking is used.
+/// Time complexity:
+///   O((V + E) * (C + 1)) where C = # of cycles, when unbounded.
+pub fn elementary_cycles<G: Adjacency>(graph: &G, limits: CycleLimits) -> Vec<Vec<G::NodeId>> {
+    let n = graph.node_count();
+    let mut doubled = Vec::new();
+    let succs: Vec<Vec<usize>> = (0..n)
+        .map(|p| {
+            let mut s = graph.out_neighbors(p);
+            s.sort_unstable();
+            if !graph.is_directed() {
+                doubled.extend(s.windows(2).filter(|w| w[0] == w[1] && p < w[0]).map(|w| (p, w[0])));
+            }
+            s.dedup();
+            s
+        })
+        .collect();
+
+    let mut search = Johnson {
+        succs: &succs,
+        start: 0,
+        in_scc: vec![false; n],
+        blocked: vec![false; n],
+        blocked_by: vec![Vec::new(); n],
+        stack: Vec::new(),
+        cycles: Vec::new(),
+        limits,
+        undirected: !graph.is_directed(),
+        doubled,
+    };
+    for s in 0..n {
+        if search.full() {
+            break;
+        }
+        search.start = s;
+        search.in_scc = scc_of(&succs, s);
+        if limits.max_len.is_some() {
+            search.backtrack(s);
+        } else {
+            search.blocked.iter_mut().for_each(|b| *b = false);
+            search.blocked_by.iter_mut().for_each(|b| b.clear());
+            search.circuit(s);
+        }
+    }
+
+    search
+        .cycles
+        .into_iter()
+        .map(|c| c.into_iter().map(|p| graph.node_id(p)).collect())
+        .collect()
+}
+
+/// Nodes `>= s` on a cycle through `s` using only nodes `>= s`.
+fn scc_of(succs: &[Vec<usize>], s: usize) -> Vec<bool> {
+    let n = succs.len();
+    let mut preds = vec![Vec::new(); n];
+    for (u, list) in succs.iter().enumerate().skip(s) {
+        for &v in list.iter().filter(|&&v| v >= s) {
+            preds[v].push(u);
+        }
+    }
+    let reach = |adj: &[Vec<usize>]| {
+        let mut seen = vec![false; n];
+        let mut frontier = VecDeque::new();
+        seen[s] = true;
+        frontier.push_back(s);
+        while let Some(at) = frontier.pop_front() {
+            for &next in adj[at].iter().filter(|&&v| v >= s) {
+                if !seen[next] {
+                    seen[next] = true;
+                    frontier.push_back(next);
+                }
+            }
+        }
+        seen
+    };
+    let fwd = reach(succs);
+    let bwd = reach(&preds);
+    fwd.into_iter().zip(bwd).map(|(a, b)| a && b).collect()
+}
+
+struct Johnson<'a> {
+    succs: &'a [Vec<usize>],
+    start: usize,
+    in_scc: Vec<bool>,
+    blocked: Vec<bool>,
+    blocked_by: Vec<Vec<usize>>,
+    stack: Vec<usize>,
+    cycles: Vec<Vec<usize>>,
+    limits: CycleLimits,
+    undirected: bool,
+    /// Undirected edges `(u, v)`, `u < v`, present more than once.
+    doubled: Vec<(usize, usize)>,
+}
+
+impl Johnson<'_> {
+    fn full(&self) -> bool {
+        self.limits.max_count.is_some_and(|m| self.cycles.len() >= m)
+    }
+
+    /// Records the cycle on the stack. An undirected cycle is also
+    /// found backwards, so only the direction leaving `start` for
+    /// the smaller neighbour is kept.
+    fn record(&mut self) {
+        let c = &self.stack;
+        let keep = !self.undirected
+            || match c.len() {
+                1 => true,
+                2 => self.doubled.contains(&(c[0], c[1])),
+                len => c[1] < c[len - 1],
+            };
+        if keep {
+            self.cycles.push(c.clone());
+        }
+    }
+
+    fn circuit(&mut self, v: usize) -> bool {
+        let mut found = false;
+        self.stack.push(v);
+        self.blocked[v] = true;
+        let succs = self.succs;
+        for &w in succs[v].iter() {
+            if !self.in_scc[w] {
+                continue;
+            }
+            if self.full() {
+                break;
+            }
+            if w == self.start {
+                self.record();
+                found = true;
+            } else if !self.blocked[w] && self.circuit(w) {
+                found = true;
+            }
+        }
+
+        if found {
+            self.unblock(v);
+        } else {
+            for &w in succs[v].iter() {
+                if self.in_scc[w] && !self.blocked_by[w].contains(&v) {
+                    self.blocked_by[w].push(v);
+                }
+            }
+        }
+        self.stack.pop();
+        found
+    }
+
+    fn unblock(&mut self, u: usize) {
+        let mut pending = vec![u];
+        while let Some(u) = pending.pop() {
+            self.blocked[u] = false;
+            for w in std::mem::take(&mut self.blocked_by[u]) {
+                if self.blocked[w] {
+                    pending.push(w);
+                }
+            }
+        }
+    }
+
+    fn backtrack(&mut self, v: usize) {
+        let max_len = self.limits.max_len.unwrap_or(usize::MAX);
+        self.stack.push(v);
+        let succs = self.succs;
+        for &w in succs[v].iter() {
+            if !self.in_scc[w] {
+                continue;
+            }
+            if self.full() {
+                break;
+            }
+            if w == self.start {
+                self.record();
+            } else if !self.stack.contains(&w) && self.stack.len() < max_len {
+                self.backtrack(w);
+            }
+        }
+        self.stack.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph, Undirected};
+
+    use super::*;
+
+    /// The complete directed graph on `n` nodes
+    /// with no self loops.
+    fn complete(n: usize) -> Graph<(), Directed> {
+        let mut g = Graph::<(), Directed>::new();
+        for _ in 0..n {
+            g.add_node(());
+        }
+        for u in 0..n {
+            for v in (0..n).filter(|&v| v != u) {
+                g.add_edge(u, v, 1);
+            }
+        }
+        g
+    }
+
+    #[test]
+    fn complete_graph_count() {
+        // K4 has 6 two-cycles, 8 three-cycles
+        // and 6 four-cycles.
+        let g = complete(4);
+        let all = elementary_cycles(&g, CycleLimits::default());
+        assert_eq!(all.len(), 20);
+        assert!(all.iter().all(|c| c[0] == *c.iter().min().unwrap()));
+
+        let short = elementary_cycles(&g, CycleLimits { max_len: Some(3), ..Default::default() });
+        assert_eq!(short.len(), 14);
+
+        let few = elementary_cycles(&g, CycleLimits { max_count: Some(5), ..Default::default() });
+        assert_eq!(few.len(), 5);
+    }
+
+    #[test]
+    fn self_loop_and_chain() {
+        let mut g = Graph::<(), Directed>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        g.add_edge(a, a, 1);
+        g.add_edge(a, b, 1);
+        g.add_edge(b, c, 1);
+        g.add_edge(c, b, 1);
+        let cycles = elementary_cycles(&g, CycleLimits::default());
+        assert_eq!(cycles, vec![vec![a], vec![b, c]]);
+    }
+
+    #[test]
+    fn undirected_once() {
+        // A triangle with a doubled tail edge: the triangle is
+        // found once and the tail as a cycle of two.
+        let mut g = Graph::<(), Undirected>::new();
+        let v: Vec<usize> = (0..4).map(|_| g.add_node(())).collect();
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 2)] {
+            g.add_edge(v[a], v[b], 1);
+        }
+        let cycles = elementary_cycles(&g, CycleLimits::default());
+        assert_eq!(cycles, vec![vec![v[0], v[1], v[2]], vec![v[2], v[3]]]);
+        let short = elementary_cycles(&g, CycleLimits { max_len: Some(3), ..Default::default() });
+        assert_eq!(short, cycles);
+    }
+}
@@ -0,0 +1,4 @@
+//! Finding and enumerating cycles.
+pub mod find;
+pub mod girth;
+pub mod johnson;
@@ -0,0 +1,164 @@
+use crate::adjacency::Adjacency;
+
+use super::tree::{successors_and_predecessors, Dominators};
+
+const NONE: usize = usize::MAX;
+
+/// Computes the immediate dominators of a directed flow graph
+/// using the Lengauer-Tarjan algorithm: semi-dominators are found
+/// in reverse dfs order over a path compressed forest, then
+/// corrected into immediate dominators in a final pass.
+/// Time complexity:
+///   O(E log V)
+pub fn lengauer_tarjan<G: Adjacency>(graph: &G, root: G::NodeId) -> Dominators<G::NodeId> {
+    let (succs, preds) = successors_and_predecessors(graph);
+    let root = graph.node_pos(root);
+    let idom = lengauer_tarjan_positions(&succs, &preds, root);
+    Dominators::new(graph, root, idom, preds)
+}
+
+pub(crate) fn lengauer_tarjan_positions(
+    succs: &[Vec<usize>],
+    preds: &[Vec<usize>],
+    root: usize,
+) -> Vec<Option<usize>> {
+    let n = succs.len();
+
+    // Number the reachable nodes in dfs preorder; everything
+    // below works on those numbers.
+    let mut dfnum = vec![NONE; n];
+    let mut vertex = Vec::new();
+    let mut parent = Vec::new();
+    let mut stack = vec![(root, NONE)];
+    while let Some((at, from)) = stack.pop() {
+        if dfnum[at] != NONE {
+            continue;
+        }
+        dfnum[at] = vertex.len();
+        vertex.push(at);
+        parent.push(if from == NONE { NONE } else { dfnum[from] });
+        for &next in succs[at].iter().rev() {
+            if dfnum[next] == NONE {
+                stack.push((next, at));
+            }
+        }
+    }
+
+    let count = vertex.len();
+    let mut semi: Vec<usize> = (0..count).collect();
+    let mut label: Vec<usize> = (0..count).collect();
+    let mut ancestor = vec![NONE; count];
+    let mut idom = vec![NONE; count];
+    let mut bucket = vec![Vec::new(); count];
+
+    for w in (1..count).rev() {
+        for &v in preds[vertex[w]].iter() {
+            if dfnum[v] == NONE {
+                continue;
+            }
+            let u = eval(dfnum[v], &mut ancestor, &mut label, &semi);
+            semi[w] = semi[w].min(semi[u]);
+        }
+        bucket[semi[w]].push(w);
+        let p = parent[w];
+        ancestor[w] = p;
+        for v in std::mem::take(&mut bucket[p]) {
+            let u = eval(v, &mut ancestor, &mut label, &semi);
+            idom[v] = if semi[u] < semi[v] { u } else { p };
+        }
+    }
+    for w in 1..count {
+        if idom[w] != semi[w] {
+            idom[w] = idom[idom[w]];
+        }
+    }
+
+    let mut out = vec![None; n];
+    for w in 1..count {
+        out[vertex[w]] = Some(vertex[idom[w]]);
+    }
+    out
+}
+
+/// The node with the smallest semi-dominator on the forest path
+/// above `v`, compressing the path as it goes.
+fn eval(v: usize, ancestor: &mut [usize], label: &mut [usize], semi: &[usize]) -> usize {
+    if ancestor[v] == NONE {
+        return v;
+    }
+
+    // Collect the path to the forest root, then compress
+    // it top down so no recursion is needed.
+    let mut path = Vec::new();
+    let mut curr = v;
+    while ancestor[ancestor[curr]] != NONE {
+        path.push(curr);
+        curr = ancestor[curr];
+    }
+    for &x in path.iter().rev() {
+        let a = ancestor[x];
+        if semi[label[a]] < semi[label[x]] {
+            label[x] = label[a];
+        }
+        ancestor[x] = ancestor[a];
+    }
+
+    label[v]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph};
+
+    use super::*;
+
+    #[test]
+    fn lengauer_tarjan_paper() {
+        // The example flow graph from Lengauer & Tarjan (1979).
+        let names = "RABCDEFGHIJKL";
+        let mut g = Graph::<char, Directed>::new();
+        for c in names.chars() {
+            g.add_node(c);
+        }
+        let at = |c: char| names.find(c).unwrap();
+        for (from, to) in [
+            ('R', 'A'), ('R', 'B'), ('R', 'C'), ('A', 'D'), ('B', 'A'),
+            ('B', 'D'), ('B', 'E'), ('C', 'F'), ('C', 'G'), ('D', 'L'),
+            ('E', 'H'), ('F', 'I'), ('G', 'I'), ('G', 'J'), ('H', 'E'),
+            ('H', 'K'), ('I', 'K'), ('J', 'I'), ('K', 'I'), ('K', 'R'),
+            ('L', 'H'),
+        ] {
+            g.add_edge(at(from), at(to), 1);
+        }
+        let dom = lengauer_tarjan(&g, at('R'));
+        let idom = |c: char| dom.immediate_dominator(at(c)).map(|p| g[p].data);
+        assert_eq!(idom('R'), None);
+        for c in "ABCDEHIKL".chars() {
+            let exp = match c {
+                'L' => 'D',
+                _ => 'R',
+            };
+            assert_eq!(idom(c), Some(exp), "idom of {}", c);
+        }
+        assert_eq!(idom('F'), Some('C'));
+        assert_eq!(idom('G'), Some('C'));
+        assert_eq!(idom('J'), Some('G'));
+        assert!(dom.dominates(at('C'), at('J')));
+        assert!(!dom.dominates(at('C'), at('I')));
+    }
+
+    #[test]
+    fn unreachable() {
+        let mut g = Graph::<(), Directed>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        g.add_edge(a, b, 1);
+        g.add_edge(c, b, 1);
+        let dom = lengauer_tarjan(&g, a);
+        assert_eq!(dom.immediate_dominator(b), Some(a));
+        assert!(!dom.is_reachable(c));
+        assert_eq!(dom.dominators(c), None);
+        assert_eq!(dom.tree().edges(a).len(), 1);
+    }
+}
@@ -0,0 +1,4 @@
+//! Dominator trees of directed flow graphs.
+pub mod tree;
+pub mod lengauer_tarjan;
+pub mod iterative;
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use crate::adjacency::Adjacency;
+use crate::list_graph::graph::{Directed, Graph};
+
+use super::lengauer_tarjan::lengauer_tarjan_positions;
+
+/// The immediate dominator of every node reachable from a root,
+/// as computed by `lengauer_tarjan` or `cooper_harvey_kennedy`.
+#[derive(Debug, Clone)]
+pub struct Dominators<I> {
+    pub(crate) root: usize,
+    /// Immediate dominator by position; `None` for the root
+    /// and for unreachable nodes.
+    pub(crate) idom: Vec<Option<usize>>,
+    /// Predecessors by position, in the direction analysed.
+    pub(crate) preds: Vec<Vec<usize>>,
+    pub(crate) ids: Vec<I>,
+    pub(crate) pos: HashMap<I, usize>,
+}
+
+impl<I> Dominators<I>
+where
+    I: Copy + Eq + std::hash::Hash,
+{
+    pub(crate) fn new<G>(graph: &G, root: usize, idom: Vec<Option<usize>>, preds: Vec<Vec<usize>>) -> Self
+    where
+        G: Adjacency<NodeId = I>,
+    {
+        let ids: Vec<I> = (0..graph.node_count()).map(|p| graph.node_id(p)).collect();
+        let pos = ids.iter().enumerate().map(|(p, &id)| (id, p)).collect();
+        Self {
+            root,
+            idom,
+            preds,
+            ids,
+            pos,
+        }
+    }
+
+    pub fn root(&self) -> I {
+        self.ids[self.root]
+    }
+
+    /// The closest strict dominator of `node`, or `None` for the
+    /// root and nodes unreachable from it.
+    pub fn immediate_dominator(&self, node: I) -> Option<I> {
+        self.idom[self.pos[&node]].map(|p| self.ids[p])
+    }
+
+    /// Whether `node` can be reached from the root.
+    pub fn is_reachable(&self, node: I) -> bool {
+        let p = self.pos[&node];
+        p == self.root || self.idom[p].is_some()
+    }
+
+    /// All dominators of `node`, starting with itself and
+    /// ending with the root. `None` if `node` is unreachable.
+    pub fn dominators(&self, node: I) -> Option<Vec<I>> {
+        if !self.is_reachable(node) {
+            return None;
+        }
+
+        let mut chain = vec![node];
+        let mut curr = self.pos[&node];
+        while let Some(up) = self.idom[curr] {
+            chain.push(self.ids[up]);
+            curr = up;
+        }
+        Some(chain)
+    }
+
+    /// Whether every path from the root to `b` passes through `a`.
+    pub fn dominates(&self, a: I, b: I) -> bool {
+        self.dominators(b).is_some_and(|chain| chain.contains(&a))
+    }
+
+    /// The dominator tree as a graph with an edge from every
+    /// immediate dominator to the nodes it dominates. Node
+    /// positions match the original graph's and each node holds
+    /// its original index.
+    pub fn tree(&self) -> Graph<I, Directed, ()> {
+        let mut tree = Graph::<I, Directed, ()>::new();
+        for &id in self.ids.iter() {
+            tree.add_node(id);
+        }
+        for (p, idom) in self.idom.iter().enumerate() {
+            if let Some(up) = idom {
+                tree.add_edge(*up, p, ());
+            }
+        }
+
+        tree
+    }
+
+    /// The dominance frontier of every node, indexed by position:
+    /// the nodes where its dominance ends, i.e. that it does not
+    /// strictly dominate but has an edge into.
+    pub fn dominance_frontiers(&self) -> Vec<Vec<I>> {
+        let reachable = |p: usize| p == self.root || self.idom[p].is_some();
+        let mut frontiers: Vec<Vec<usize>> = vec![Vec::new(); self.ids.len()];
+        for b in (0..self.ids.len()).filter(|&b| reachable(b)) {
+            let preds: Vec<usize> = self.preds[b].iter().cloned().filter(|&p| reachable(p)).collect();
+            if preds.len() < 2 {
+                continue;
+            }
+            for p in preds {
+                let mut runner = Some(p);
+                while let Some(r) = runner {
+                    if Some(r) == self.idom[b] {
+                        break;
+                    }
+                    if !frontiers[r].contains(&b) {
+                        frontiers[r].push(b);
+                    }
+                    runner = self.idom[r];
+                }
+            }
+        }
+
+        frontiers
+            .into_iter()
+            .map(|mut f| {
+                f.sort_unstable();
+                f.into_iter().map(|p| self.ids[p]).collect()
+            })
+            .collect()
+    }
+}
+
+/// Computes post-dominators: the dominators of the reversed
+/// graph, rooted at `exit`. A node post-dominates another if
+/// every path from the latter to `exit` passes through it.
+pub fn post_dominators<G: Adjacency>(graph: &G, exit: G::NodeId) -> Dominators<G::NodeId> {
+    let (succs, preds) = successors_and_predecessors(graph);
+    let root = graph.node_pos(exit);
+    let idom = lengauer_tarjan_positions(&preds, &succs, root);
+    Dominators::new(graph, root, idom, succs)
+}
+
+pub(crate) fn successors_and_predecessors<G: Adjacency>(graph: &G) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
+    let n = graph.node_count();
+    let succs: Vec<Vec<usize>> = (0..n).map(|p| graph.out_neighbors(p)).collect();
+    let mut preds = vec![Vec::new(); n];
+    for (p, list) in succs.iter().enumerate() {
+        for &s in list.iter() {
+            preds[s].push(p);
+        }
+    }
+
+    (succs, preds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diamond_post_dominators() {
+        //     a
+        //    / \
+        //   b   c
+        //    \ /
+        //     d
+        let mut g = Graph::<(), Directed>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        let d = g.add_node(());
+        g.add_edge(a, b, 1);
+        g.add_edge(a, c, 1);
+        g.add_edge(b, d, 1);
+        g.add_edge(c, d, 1);
+        let pdom = post_dominators(&g, d);
+        assert_eq!(pdom.immediate_dominator(a), Some(d));
+        assert_eq!(pdom.immediate_dominator(b), Some(d));
+        assert_eq!(pdom.immediate_dominator(d), None);
+        assert_eq!(pdom.dominance_frontiers()[b], vec![a]);
+    }
+}
@@ -0,0 +1,202 @@
+use std::fmt;
+
+use crate::adjacency::{self, Adjacency};
+
+/// Why a graph has no Eulerian trail or circuit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EulerError<I> {
+    /// These nodes break the degree condition: odd degree in an
+    /// undirected graph, in-degree differing from out-degree in a
+    /// directed one (beyond the start and end a trail may have).
+    Imbalanced(Vec<I>),
+    /// The edges do not all lie in one connected component.
+    Disconnected,
+}
+
+impl<I: fmt::Debug> fmt::Display for EulerError<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EulerError::Imbalanced(nodes) => write!(f, "degree imbalance at nodes {:?}", nodes),
+            EulerError::Disconnected => write!(f, "edges span more than one connected component"),
+        }
+    }
+}
+
+impl<I: fmt::Debug> std::error::Error for EulerError<I> {}
+
+/// Finds an Eulerian circuit, a closed walk using every edge
+/// exactly once, with Hierholzer's algorithm. The circuit is
+/// returned as its nodes, starting and ending at the same one;
+/// a graph without edges yields an empty circuit.
+/// Time complexity:
+///   adj-matrix graph: O(V^2)
+///   adj-list graph: O(V + E)
+pub fn eulerian_circuit<G: Adjacency>(graph: &G) -> Result<Vec<G::NodeId>, EulerError<G::NodeId>> {
+    let edges = edge_list(graph);
+    let balance = balance(graph, &edges);
+    let bad: Vec<usize> = (0..balance.len()).filter(|&p| balance[p] != 0).collect();
+    if !bad.is_empty() {
+        return Err(EulerError::Imbalanced(bad.into_iter().map(|p| graph.node_id(p)).collect()));
+    }
+
+    let start = edges.first().map(|e| e.0);
+    walk(graph, &edges, start)
+}
+
+/// Finds an Eulerian trail, a walk using every edge exactly
+/// once, with Hierholzer's algorithm. The trail is closed
+/// whenever the graph also has an Eulerian circuit.
+/// Time complexity:
+///   adj-matrix graph: O(V^2)
+///   adj-list graph: O(V + E)
+pub fn eulerian_path<G: Adjacency>(graph: &G) -> Result<Vec<G::NodeId>, EulerError<G::NodeId>> {
+    let edges = edge_list(graph);
+    let balance = balance(graph, &edges);
+    let bad: Vec<usize> = (0..balance.len()).filter(|&p| balance[p] != 0).collect();
+
+    let start = if bad.is_empty() {
+        edges.first().map(|e| e.0)
+    } else if graph.is_directed() {
+        // exactly one node with a surplus of one outgoing edge,
+        // and one with a surplus of one incoming edge
+        let starts: Vec<usize> = bad.iter().cloned().filter(|&p| balance[p] == 1).collect();
+        let ends = bad.iter().filter(|&&p| balance[p] == -1).count();
+        if bad.len() != 2 || starts.len() != 1 || ends != 1 {
+            return Err(EulerError::Imbalanced(bad.into_iter().map(|p| graph.node_id(p)).collect()));
+        }
+        Some(starts[0])
+    } else {
+        if bad.len() != 2 {
+            return Err(EulerError::Imbalanced(bad.into_iter().map(|p| graph.node_id(p)).collect()));
+        }
+        Some(bad[0])
+    };
+
+    walk(graph, &edges, start)
+}
+
+/// Every edge once, as `(from, to)`.
+fn edge_list<G: Adjacency>(graph: &G) -> Vec<(usize, usize)> {
+    adjacency::edge_list(graph).into_iter().map(|(u, v, _)| (u, v)).collect()
+}
+
+/// Out minus in degree when directed; degree parity otherwise.
+fn balance<G: Adjacency>(graph: &G, edges: &[(usize, usize)]) -> Vec<i64> {
+    let mut balance = vec![0i64; graph.node_count()];
+    for &(u, v) in edges.iter() {
+        if graph.is_directed() {
+            balance[u] += 1;
+            balance[v] -= 1;
+        } else {
+            balance[u] += 1;
+            balance[v] += 1;
+        }
+    }
+    if !graph.is_directed() {
+        balance.iter_mut().for_each(|b| *b %= 2);
+    }
+    balance
+}
+
+fn walk<G: Adjacency>(
+    graph: &G,
+    edges: &[(usize, usize)],
+    start: Option<usize>,
+) -> Result<Vec<G::NodeId>, EulerError<G::NodeId>> {
+    let Some(start) = start else {
+        return Ok(Vec::new());
+    };
+
+    let mut adj = vec![Vec::new(); graph.node_count()];
+    for (i, &(u, v)) in edges.iter().enumerate() {
+        adj[u].push(i);
+        if !graph.is_directed() && u != v {
+            adj[v].push(i);
+        }
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut next = vec![0; graph.node_count()];
+    let mut stack = vec![start];
+    let mut trail = Vec::new();
+    while let Some(&at) = stack.last() {
+        while next[at] < adj[at].len() && used[adj[at][next[at]]] {
+            next[at] += 1;
+        }
+        if let Some(&e) = adj[at].get(next[at]) {
+            used[e] = true;
+            let (u, v) = edges[e];
+            stack.push(if u == at { v } else { u });
+        } else {
+            trail.push(at);
+            stack.pop();
+        }
+    }
+
+    // Edges out of reach of the start were never walked.
+    if trail.len() != edges.len() + 1 {
+        return Err(EulerError::Disconnected);
+    }
+    trail.reverse();
+    Ok(trail.into_iter().map(|p| graph.node_id(p)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph};
+
+    use super::*;
+
+    #[test]
+    fn konigsberg() {
+        // The seven bridges: every land mass has odd degree.
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..4).map(|_| g.add_node(())).collect();
+        for &(a, b) in &[(0, 1), (0, 1), (0, 2), (0, 2), (0, 3), (1, 3), (2, 3)] {
+            g.add_edge(v[a], v[b], 1);
+        }
+        assert_eq!(eulerian_path(&g), Err(EulerError::Imbalanced(v.clone())));
+        assert_eq!(
+            eulerian_circuit(&g).unwrap_err().to_string(),
+            "degree imbalance at nodes [0, 1, 2, 3]"
+        );
+    }
+
+    #[test]
+    fn undirected_trail() {
+        // A house shape: the two floor corners are odd.
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..5).map(|_| g.add_node(())).collect();
+        for &(a, b) in &[(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (2, 4), (4, 3)] {
+            g.add_edge(v[a], v[b], 1);
+        }
+        let trail = eulerian_path(&g).unwrap();
+        assert_eq!(trail.len(), 8);
+        assert!(eulerian_circuit(&g).is_err());
+    }
+
+    #[test]
+    fn directed_circuit_and_trail() {
+        let mut g = Graph::<(), Directed>::new();
+        let v: Vec<usize> = (0..3).map(|_| g.add_node(())).collect();
+        g.add_edge(v[0], v[1], 1);
+        g.add_edge(v[1], v[2], 1);
+        g.add_edge(v[2], v[0], 1);
+        g.add_edge(v[1], v[1], 1);
+        let circuit = eulerian_circuit(&g).unwrap();
+        assert_eq!(circuit, vec![v[0], v[1], v[1], v[2], v[0]]);
+
+        g.add_edge(v[0], v[2], 1);
+        assert_eq!(eulerian_path(&g).unwrap(), vec![v[0], v[1], v[1], v[2], v[0], v[2]]);
+    }
+
+    #[test]
+    fn disconnected_edges() {
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..6).map(|_| g.add_node(())).collect();
+        for &(a, b) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)] {
+            g.add_edge(v[a], v[b], 1);
+        }
+        assert_eq!(eulerian_circuit(&g), Err(EulerError::Disconnected));
+    }
+}
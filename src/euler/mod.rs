@@ -0,0 +1,2 @@
+//! Eulerian trails and circuits.
+pub mod hierholzer;
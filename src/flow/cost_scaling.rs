@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+
+use crate::adjacency::Adjacency;
+
+use super::network::{FlowNetwork, MinCostFlow};
+
+/// Computes a minimum cost maximum flow from `source` to `sink`
+/// with Goldberg-Tarjan cost scaling.
+///
+/// The problem is turned into a minimum cost circulation by
+/// adding a `sink -> source` arc whose cost is negative enough
+/// that pushing more flow always pays off, then the circulation
+/// is refined with push-relabel for a shrinking optimality
+/// tolerance `eps`. Unlike successive shortest paths this also
+/// copes with negative cost cycles.
+///
+/// `cap_cost` maps an edge weight to its `(capacity, cost)`.
+/// Time complexity:
+///   O(V^3 * log(V * C)) where C = the largest absolute cost.
+pub fn cost_scaling<G, F>(
+    graph: &G,
+    source: G::NodeId,
+    sink: G::NodeId,
+    cap_cost: F,
+) -> MinCostFlow<G::NodeId>
+where
+    G: Adjacency,
+    F: Fn(G::Weight) -> (i64, i64),
+{
+    let mut net = FlowNetwork::with_costs(graph, cap_cost);
+    let (s, t) = (graph.node_pos(source), graph.node_pos(sink));
+    run(&mut net, s, t);
+    net.cost_result(s)
+}
+
+pub(crate) fn run<I: Copy>(net: &mut FlowNetwork<I>, s: usize, t: usize) {
+    if s == t {
+        return;
+    }
+
+    let n = net.len() as i64;
+    let max_cost = net.arcs.iter().map(|a| a.cost.abs()).max().unwrap_or(0);
+    let bound: i64 = net.adj[s].iter().map(|&a| net.arcs[a].cap).sum();
+    let back = net.add_arc(t, s, bound, -(n * max_cost + 1));
+
+    // Scaling every cost by n + 1 makes a 1-optimal
+    // circulation exactly optimal.
+    let cost: Vec<i64> = net.arcs.iter().map(|a| a.cost * (n + 1)).collect();
+    let mut price = vec![0i64; net.len()];
+    let mut eps = cost.iter().map(|c| c.abs()).max().unwrap_or(1).max(1);
+    loop {
+        eps = (eps / 2).max(1);
+        refine(net, &cost, &mut price, eps);
+        if eps == 1 {
+            break;
+        }
+    }
+
+    // Drop the helper arc again.
+    net.arcs.truncate(back);
+    net.adj[t].pop();
+    net.adj[s].pop();
+}
+
+/// Turns an `2 * eps`-optimal circulation into an `eps`-optimal one.
+fn refine<I: Copy>(net: &mut FlowNetwork<I>, cost: &[i64], price: &mut [i64], eps: i64) {
+    let reduced = |a: usize, net: &FlowNetwork<I>, price: &[i64]| {
+        cost[a] + price[net.arcs[a].from] - price[net.arcs[a].to]
+    };
+
+    let mut excess = vec![0i64; net.len()];
+    for a in 0..net.arcs.len() {
+        let residual = net.arcs[a].residual();
+        if residual > 0 && reduced(a, net, price) < 0 {
+            net.push(a, residual);
+            excess[net.arcs[a].from] -= residual;
+            excess[net.arcs[a].to] += residual;
+        }
+    }
+
+    let mut active: VecDeque<usize> = (0..net.len()).filter(|&v| excess[v] > 0).collect();
+    let mut current = vec![0; net.len()];
+    while let Some(u) = active.pop_front() {
+        while excess[u] > 0 {
+            if current[u] == net.adj[u].len() {
+                // relabel
+                price[u] = net.adj[u]
+                    .iter()
+                    .filter(|&&a| net.arcs[a].residual() > 0)
+                    .map(|&a| price[net.arcs[a].to] - cost[a])
+                    .max()
+                    .unwrap_or(price[u])
+                    - eps;
+                current[u] = 0;
+                continue;
+            }
+
+            let a = net.adj[u][current[u]];
+            let residual = net.arcs[a].residual();
+            if residual > 0 && reduced(a, net, price) < 0 {
+                let to = net.arcs[a].to;
+                let amount = residual.min(excess[u]);
+                net.push(a, amount);
+                excess[u] -= amount;
+                let was_active = excess[to] > 0;
+                excess[to] += amount;
+                if !was_active && excess[to] > 0 {
+                    active.push_back(to);
+                }
+            } else {
+                current[u] += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flow::ssp::successive_shortest_path;
+    use crate::list_graph::graph::{Directed, Graph};
+
+    use super::*;
+
+    #[test]
+    fn matches_ssp() {
+        let mut g = Graph::<(), Directed, (i64, i64)>::new();
+        let n: Vec<usize> = (0..6).map(|_| g.add_node(())).collect();
+        for &(a, b, cap, cost) in &[
+            (0, 1, 4, 2), (0, 2, 3, 1), (1, 2, 2, 1), (1, 3, 3, 4),
+            (2, 3, 1, 2), (2, 4, 4, 3), (3, 5, 5, 1), (4, 3, 2, 1),
+            (4, 5, 3, 6),
+        ] {
+            g.add_edge(n[a], n[b], (cap, cost));
+        }
+        let exp = successive_shortest_path(&g, n[0], n[5], |w| w);
+        let flow = cost_scaling(&g, n[0], n[5], |w| w);
+        assert_eq!(flow.value, exp.value);
+        assert_eq!(flow.cost, exp.cost);
+        assert_eq!(flow.flows.len(), 9);
+    }
+
+    #[test]
+    fn negative_cycle() {
+        // a <-> b is a profitable loop that carries
+        // flow regardless of the s-t demand.
+        let mut g = Graph::<(), Directed, (i64, i64)>::new();
+        let s = g.add_node(());
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let t = g.add_node(());
+        g.add_edge(s, a, (1, 1));
+        g.add_edge(a, t, (1, 1));
+        g.add_edge(a, b, (2, -3));
+        g.add_edge(b, a, (2, 1));
+        let flow = cost_scaling(&g, s, t, |w| w);
+        assert_eq!(flow.value, 1);
+        assert_eq!(flow.cost, 2 + 2 * -2);
+    }
+}
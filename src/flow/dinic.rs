@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+
+use crate::adjacency::{Adjacency, Measure};
+
+use super::network::{FlowNetwork, MaxFlow};
+
+/// Computes the maximum flow from `source` to `sink` using
+/// Dinic's algorithm: repeatedly builds a bfs level graph and
+/// saturates it with a blocking flow.
+/// Edge weights are read as capacities.
+/// Time complexity:
+///   O(V^2 * E)
+pub fn dinic<G>(graph: &G, source: G::NodeId, sink: G::NodeId) -> MaxFlow<G::NodeId>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let mut net = FlowNetwork::new(graph);
+    let (s, t) = (graph.node_pos(source), graph.node_pos(sink));
+    run(&mut net, s, t);
+    net.result(s)
+}
+
+/// Saturates `net` from `s` to `t`, returning the flow value.
+pub(crate) fn run<I: Copy>(net: &mut FlowNetwork<I>, s: usize, t: usize) -> i64 {
+    if s == t {
+        return 0;
+    }
+
+    let mut total = 0;
+    while let Some(level) = levels(net, s, t) {
+        // Per-node pointer to the next arc worth trying, so
+        // dead ends are never revisited within a phase.
+        let mut next = vec![0; net.len()];
+        loop {
+            let pushed = blocking(net, &level, &mut next, s, t, i64::MAX);
+            if pushed == 0 {
+                break;
+            }
+            total += pushed;
+        }
+    }
+
+    total
+}
+
+/// Bfs distances from `s` over residual arcs, or `None`
+/// when `t` is no longer reachable.
+fn levels<I: Copy>(net: &FlowNetwork<I>, s: usize, t: usize) -> Option<Vec<usize>> {
+    let mut level = vec![usize::MAX; net.len()];
+    level[s] = 0;
+    let mut frontier = VecDeque::new();
+    frontier.push_back(s);
+    while let Some(at) = frontier.pop_front() {
+        for &a in net.adj[at].iter() {
+            let arc = &net.arcs[a];
+            if arc.residual() > 0 && level[arc.to] == usize::MAX {
+                level[arc.to] = level[at] + 1;
+                frontier.push_back(arc.to);
+            }
+        }
+    }
+
+    if level[t] == usize::MAX {
+        None
+    } else {
+        Some(level)
+    }
+}
+
+fn blocking<I: Copy>(
+    net: &mut FlowNetwork<I>,
+    level: &[usize],
+    next: &mut [usize],
+    at: usize,
+    t: usize,
+    limit: i64,
+) -> i64 {
+    if at == t {
+        return limit;
+    }
+
+    while next[at] < net.adj[at].len() {
+        let a = net.adj[at][next[at]];
+        let (to, residual) = (net.arcs[a].to, net.arcs[a].residual());
+        if residual > 0 && level[to] == level[at] + 1 {
+            let pushed = blocking(net, level, next, to, t, limit.min(residual));
+            if pushed > 0 {
+                net.push(a, pushed);
+                return pushed;
+            }
+        }
+        next[at] += 1;
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flow::network::clrs_example;
+    use crate::list_graph::graph::{Directed, Graph};
+
+    use super::*;
+
+    #[test]
+    fn clrs_network() {
+        let (g, [s, _, _, _, _, t]) = clrs_example();
+        assert_eq!(dinic(&g, s, t).value, 23);
+    }
+
+    #[test]
+    fn parallel_edges() {
+        let mut g = Graph::<(), Directed>::new();
+        let s = g.add_node(());
+        let t = g.add_node(());
+        g.add_edge(s, t, 2);
+        g.add_edge(s, t, 3);
+        let flow = dinic(&g, s, t);
+        assert_eq!(flow.value, 5);
+        assert_eq!(flow.flows.len(), 2);
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+
+use crate::adjacency::{Adjacency, Measure};
+
+use super::network::{FlowNetwork, MaxFlow};
+
+/// Computes the maximum flow from `source` to `sink` using
+/// the Edmonds-Karp algorithm, i.e. Ford-Fulkerson where every
+/// augmenting path is a shortest path found by bfs.
+/// Edge weights are read as capacities.
+/// Time complexity:
+///   O(V * E^2)
+pub fn edmonds_karp<G>(graph: &G, source: G::NodeId, sink: G::NodeId) -> MaxFlow<G::NodeId>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let mut net = FlowNetwork::new(graph);
+    let (s, t) = (graph.node_pos(source), graph.node_pos(sink));
+    run(&mut net, s, t);
+    net.result(s)
+}
+
+/// Saturates `net` from `s` to `t`, returning the flow value.
+pub(crate) fn run<I: Copy>(net: &mut FlowNetwork<I>, s: usize, t: usize) -> i64 {
+    if s == t {
+        return 0;
+    }
+
+    let mut total = 0;
+    while let Some(path) = shortest_path(net, s, t) {
+        let amount = path
+            .iter()
+            .map(|&a| net.arcs[a].residual())
+            .min()
+            .unwrap_or(0);
+        for &a in path.iter() {
+            net.push(a, amount);
+        }
+        total += amount;
+    }
+
+    total
+}
+
+/// Finds the arcs of a shortest residual path from `s` to `t`.
+fn shortest_path<I: Copy>(net: &FlowNetwork<I>, s: usize, t: usize) -> Option<Vec<usize>> {
+    let mut came_from: Vec<Option<usize>> = vec![None; net.len()];
+    let mut visited = vec![false; net.len()];
+    visited[s] = true;
+    let mut frontier = VecDeque::new();
+    frontier.push_back(s);
+    while let Some(at) = frontier.pop_front() {
+        if at == t {
+            break;
+        }
+
+        for &a in net.adj[at].iter() {
+            let arc = &net.arcs[a];
+            if arc.residual() > 0 && !visited[arc.to] {
+                visited[arc.to] = true;
+                came_from[arc.to] = Some(a);
+                frontier.push_back(arc.to);
+            }
+        }
+    }
+
+    if !visited[t] {
+        return None;
+    }
+
+    let mut path = Vec::new();
+    let mut curr = t;
+    while let Some(a) = came_from[curr] {
+        path.push(a);
+        curr = net.arcs[a].from;
+    }
+    path.reverse();
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flow::network::clrs_example;
+    use crate::list_graph::graph::{Directed, Graph};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn clrs_network() {
+        let (g, [s, _, _, _, _, t]) = clrs_example();
+        let flow = edmonds_karp(&g, s, t);
+        assert_eq!(flow.value, 23);
+        for e in flow.flows.iter() {
+            assert!(e.flow >= 0 && e.flow <= e.capacity);
+        }
+    }
+
+    #[test]
+    fn matrix_backend() {
+        let mut g = mtx::Graph::<char, mtx::Directed, mtx::Weighted>::default();
+        let s = g.add_node('s');
+        let a = g.add_node('a');
+        let t = g.add_node('t');
+        g.add_edge(s, a, 5);
+        g.add_edge(a, t, 3);
+        g.add_edge(s, t, 2);
+        let flow = edmonds_karp(&g, s, t);
+        assert_eq!(flow.value, 5);
+        let sa = flow.flows.iter().find(|e| e.from == s && e.to == a).unwrap();
+        assert_eq!(sa.flow, 3);
+    }
+
+    #[test]
+    fn disconnected() {
+        let mut g = Graph::<(), Directed>::new();
+        let s = g.add_node(());
+        let t = g.add_node(());
+        g.add_edge(t, s, 1);
+        assert_eq!(edmonds_karp(&g, s, t).value, 0);
+    }
+}
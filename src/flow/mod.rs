@@ -0,0 +1,8 @@
+//! Maximum flow and minimum cost flow algorithms over residual networks
+//! built from weighted graphs.
+pub mod network;
+pub mod edmonds_karp;
+pub mod dinic;
+pub mod push_relabel;
+pub mod ssp;
+pub mod cost_scaling;
@@ -0,0 +1,197 @@
+use crate::adjacency::{Adjacency, Measure};
+
+/// A single arc of the residual network. Arcs are stored
+/// in pairs: the arc at an even index is an edge of the
+/// original graph, the arc right after it is its reverse
+/// with zero capacity.
+#[derive(Debug, Clone)]
+pub(crate) struct Arc {
+    pub(crate) from: usize,
+    pub(crate) to: usize,
+    pub(crate) cap: i64,
+    pub(crate) flow: i64,
+    pub(crate) cost: i64,
+}
+
+impl Arc {
+    pub(crate) fn residual(&self) -> i64 {
+        self.cap - self.flow
+    }
+}
+
+/// Residual network built from any weighted graph, where
+/// edge weights are read as capacities. Nodes are addressed
+/// by position; the native indices are kept so results can
+/// be reported in the backend's terms.
+#[derive(Debug, Clone)]
+pub struct FlowNetwork<I> {
+    pub(crate) ids: Vec<I>,
+    pub(crate) adj: Vec<Vec<usize>>,
+    pub(crate) arcs: Vec<Arc>,
+}
+
+/// The flow carried by a single edge of the original graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeFlow<I> {
+    pub from: I,
+    pub to: I,
+    pub capacity: i64,
+    pub flow: i64,
+}
+
+/// Result of a maximum flow computation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaxFlow<I> {
+    pub value: i64,
+    pub flows: Vec<EdgeFlow<I>>,
+}
+
+/// Result of a minimum cost maximum flow computation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinCostFlow<I> {
+    pub value: i64,
+    pub cost: i64,
+    pub flows: Vec<EdgeFlow<I>>,
+}
+
+impl<I: Copy> FlowNetwork<I> {
+    pub fn new<G>(graph: &G) -> Self
+    where
+        G: Adjacency<NodeId = I>,
+        G::Weight: Measure,
+    {
+        Self::with_costs(graph, |w| (w.to_i64(), 0))
+    }
+
+    /// Builds a network whose edges carry a cost per unit of
+    /// flow. `cap_cost` maps an edge weight to its
+    /// `(capacity, cost)` pair.
+    pub fn with_costs<G, F>(graph: &G, cap_cost: F) -> Self
+    where
+        G: Adjacency<NodeId = I>,
+        F: Fn(G::Weight) -> (i64, i64),
+    {
+        let n = graph.node_count();
+        let mut net = Self {
+            ids: (0..n).map(|p| graph.node_id(p)).collect(),
+            adj: vec![Vec::new(); n],
+            arcs: Vec::new(),
+        };
+        for from in 0..n {
+            for (to, w) in graph.out_edges(from) {
+                let (cap, cost) = cap_cost(w);
+                net.add_arc(from, to, cap, cost);
+            }
+        }
+
+        net
+    }
+
+    /// Adds an arc and its zero capacity reverse,
+    /// returning the index of the forward arc.
+    pub(crate) fn add_arc(&mut self, from: usize, to: usize, cap: i64, cost: i64) -> usize {
+        let idx = self.arcs.len();
+        self.arcs.push(Arc { from, to, cap, flow: 0, cost });
+        self.arcs.push(Arc { from: to, to: from, cap: 0, flow: 0, cost: -cost });
+        self.adj[from].push(idx);
+        self.adj[to].push(idx + 1);
+        idx
+    }
+
+    /// Sends `amount` along arc `a`, updating its pair.
+    pub(crate) fn push(&mut self, a: usize, amount: i64) {
+        self.arcs[a].flow += amount;
+        self.arcs[a ^ 1].flow -= amount;
+    }
+
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+
+    /// Total flow leaving `source`.
+    pub(crate) fn value(&self, source: usize) -> i64 {
+        self.adj[source]
+            .iter()
+            .map(|&a| self.arcs[a].flow)
+            .sum()
+    }
+
+    /// Collects the flow of every original edge along with
+    /// the flow value out of `source`.
+    pub(crate) fn result(&self, source: usize) -> MaxFlow<I> {
+        let flows = self
+            .arcs
+            .iter()
+            .step_by(2)
+            .map(|a| EdgeFlow {
+                from: self.ids[a.from],
+                to: self.ids[a.to],
+                capacity: a.cap,
+                flow: a.flow,
+            })
+            .collect();
+        MaxFlow {
+            value: self.value(source),
+            flows,
+        }
+    }
+
+    /// Like `result`, but also totals the cost of the flow.
+    pub(crate) fn cost_result(&self, source: usize) -> MinCostFlow<I> {
+        let cost = self
+            .arcs
+            .iter()
+            .step_by(2)
+            .map(|a| a.flow * a.cost)
+            .sum();
+        let MaxFlow { value, flows } = self.result(source);
+        MinCostFlow { value, cost, flows }
+    }
+}
+
+/// The classic CLRS example network, with a maximum flow of 23,
+/// as the graph and its nodes `[s, v1, v2, v3, v4, t]`.
+#[cfg(test)]
+pub(crate) fn clrs_example() -> (
+    crate::list_graph::graph::Graph<(), crate::list_graph::graph::Directed>,
+    [usize; 6],
+) {
+    let mut g = crate::list_graph::graph::Graph::<(), crate::list_graph::graph::Directed>::new();
+    let v: Vec<usize> = (0..6).map(|_| g.add_node(())).collect();
+    let (s, v1, v2, v3, v4, t) = (v[0], v[1], v[2], v[3], v[4], v[5]);
+    g.add_edge(s, v1, 16);
+    g.add_edge(s, v2, 13);
+    g.add_edge(v2, v1, 4);
+    g.add_edge(v1, v3, 12);
+    g.add_edge(v3, v2, 9);
+    g.add_edge(v2, v4, 14);
+    g.add_edge(v4, v3, 7);
+    g.add_edge(v3, t, 20);
+    g.add_edge(v4, t, 4);
+    (g, [s, v1, v2, v3, v4, t])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph};
+
+    use super::*;
+
+    #[test]
+    fn paired_arcs() {
+        let mut g = Graph::<(), Directed>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        g.add_edge(a, b, 4);
+        let mut net = FlowNetwork::new(&g);
+        assert_eq!(net.arcs.len(), 2);
+        net.push(0, 3);
+        assert_eq!(net.arcs[0].residual(), 1);
+        assert_eq!(net.arcs[1].residual(), 3);
+        assert_eq!(net.value(a), 3);
+    }
+}
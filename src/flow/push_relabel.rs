@@ -0,0 +1,119 @@
+use std::collections::VecDeque;
+
+use crate::adjacency::{Adjacency, Measure};
+
+use super::network::{FlowNetwork, MaxFlow};
+
+/// Computes the maximum flow from `source` to `sink` using
+/// the Goldberg-Tarjan push-relabel algorithm, discharging
+/// active nodes in FIFO order.
+/// Edge weights are read as capacities.
+/// Time complexity:
+///   O(V^3)
+pub fn push_relabel<G>(graph: &G, source: G::NodeId, sink: G::NodeId) -> MaxFlow<G::NodeId>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let mut net = FlowNetwork::new(graph);
+    let (s, t) = (graph.node_pos(source), graph.node_pos(sink));
+    run(&mut net, s, t);
+    net.result(s)
+}
+
+/// Saturates `net` from `s` to `t`, returning the flow value.
+pub(crate) fn run<I: Copy>(net: &mut FlowNetwork<I>, s: usize, t: usize) -> i64 {
+    if s == t {
+        return 0;
+    }
+
+    let n = net.len();
+    let mut height = vec![0; n];
+    let mut excess = vec![0i64; n];
+    let mut current = vec![0; n];
+    let mut active = VecDeque::new();
+    height[s] = n;
+
+    for i in 0..net.adj[s].len() {
+        let a = net.adj[s][i];
+        let (to, amount) = (net.arcs[a].to, net.arcs[a].residual());
+        if amount > 0 {
+            net.push(a, amount);
+            excess[s] -= amount;
+            if excess[to] == 0 && to != t {
+                active.push_back(to);
+            }
+            excess[to] += amount;
+        }
+    }
+
+    while let Some(u) = active.pop_front() {
+        // discharge
+        while excess[u] > 0 {
+            if current[u] == net.adj[u].len() {
+                height[u] = 1 + net.adj[u]
+                    .iter()
+                    .map(|&a| &net.arcs[a])
+                    .filter(|arc| arc.residual() > 0)
+                    .map(|arc| height[arc.to])
+                    .min()
+                    .unwrap_or(2 * n);
+                current[u] = 0;
+                continue;
+            }
+
+            let a = net.adj[u][current[u]];
+            let (to, residual) = (net.arcs[a].to, net.arcs[a].residual());
+            if residual > 0 && height[u] == height[to] + 1 {
+                let amount = residual.min(excess[u]);
+                net.push(a, amount);
+                excess[u] -= amount;
+                if excess[to] == 0 && to != s && to != t {
+                    active.push_back(to);
+                }
+                excess[to] += amount;
+            } else {
+                current[u] += 1;
+            }
+        }
+    }
+
+    excess[t]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flow::network::clrs_example;
+    use crate::list_graph::graph::{Directed, Graph};
+
+    use super::*;
+
+    #[test]
+    fn clrs_network() {
+        let (g, [s, v1, v2, v3, v4, t]) = clrs_example();
+        let flow = push_relabel(&g, s, t);
+        assert_eq!(flow.value, 23);
+
+        // conservation at every inner node
+        for v in [v1, v2, v3, v4] {
+            let inflow: i64 = flow.flows.iter().filter(|e| e.to == v).map(|e| e.flow).sum();
+            let outflow: i64 = flow.flows.iter().filter(|e| e.from == v).map(|e| e.flow).sum();
+            assert_eq!(inflow, outflow);
+        }
+    }
+
+    #[test]
+    fn excess_returns_to_source() {
+        // more capacity leaves the source than
+        // can ever reach the sink.
+        let mut g = Graph::<(), Directed>::new();
+        let s = g.add_node(());
+        let a = g.add_node(());
+        let t = g.add_node(());
+        g.add_edge(s, a, 10);
+        g.add_edge(a, t, 1);
+        let flow = push_relabel(&g, s, t);
+        assert_eq!(flow.value, 1);
+        assert!(flow.flows.iter().all(|e| e.flow == 1));
+    }
+}
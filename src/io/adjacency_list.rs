@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::adjacency::{edge_list, Adjacency};
+
+use super::build::FromParts;
+use super::error::ParseError;
+use super::text::{plain_field, Labels, TextOptions};
+
+type NodeLabel<'a, N> = Box<dyn Fn(&N) -> String + 'a>;
+
+/// Reads an adjacency list: each line names a node followed by
+/// the nodes it has edges to, so a node without edges is a line of
+/// its own. Every listed edge is added once, with weight `weight`.
+/// Returns the graph together with the position of every node
+/// label.
+/// Time complexity:
+///   O(size of input)
+pub fn read_adjacency_list<G>(
+    input: &str,
+    options: &TextOptions,
+    weight: G::Weight,
+) -> Result<(G, HashMap<String, usize>), ParseError>
+where
+    G: FromParts,
+    G::Node: From<String>,
+    G::Weight: Copy,
+{
+    let mut labels = Labels::new(options);
+    let mut edges = Vec::new();
+    for (line, fields) in options.records(input) {
+        let u = labels.get(fields[0], line)?;
+        for field in fields[1..].iter().filter(|f| !f.is_empty()) {
+            edges.push((u, labels.get(field, line)?, weight));
+        }
+    }
+
+    Ok((G::from_parts(labels.names.into_iter().map(G::Node::from).collect(), edges), labels.index))
+}
+
+/// Adjacency list rendering of a graph from either backend: one
+/// line per node with the nodes it has edges to. Undirected edges
+/// are listed once, on the line of their smaller endpoint, so the
+/// text reads back into the same graph. Nodes are written as
+/// positions unless labelled. A label that would not read back as
+/// one field makes formatting fail with `fmt::Error`.
+pub struct AdjacencyList<'a, G: Adjacency> {
+    graph: &'a G,
+    delimiter: char,
+    node_label: Option<NodeLabel<'a, G::Node>>,
+}
+
+impl<'a, G: Adjacency> AdjacencyList<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        Self {
+            graph,
+            delimiter: ' ',
+            node_label: None,
+        }
+    }
+
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Names every node with `f` applied to its data.
+    pub fn node_label<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&G::Node) -> String + 'a,
+    {
+        self.node_label = Some(Box::new(f));
+        self
+    }
+}
+
+impl<G: Adjacency> fmt::Display for AdjacencyList<'_, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |p: usize| match &self.node_label {
+            Some(label) => label(self.graph.node_data(p)),
+            None => p.to_string(),
+        };
+        let mut lines = vec![Vec::new(); self.graph.node_count()];
+        for (u, v, _) in edge_list(self.graph) {
+            lines[u].push(v);
+        }
+        for (u, targets) in lines.iter().enumerate() {
+            write!(f, "{}", plain_field(name(u), self.delimiter)?)?;
+            for &v in targets {
+                write!(f, "{}{}", self.delimiter, plain_field(name(v), self.delimiter)?)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Graph, Undirected};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let text = "hub a b c\na b\nlonely\n";
+        let (g, index): (Graph<String, Undirected, ()>, _) =
+            read_adjacency_list(text, &TextOptions::default(), ()).unwrap();
+        assert_eq!(g.len(), 5);
+        assert_eq!(g.edges(index["a"]).len(), 2);
+        assert!(g.edges(index["lonely"]).is_empty());
+
+        let written = AdjacencyList::new(&g).node_label(|n| n.clone()).to_string();
+        assert_eq!(written, text.replace("lonely\n", "b\nc\nlonely\n"));
+
+        let err = read_adjacency_list::<Graph<String, Undirected, ()>>("a,b\n,b", &TextOptions::csv(), ()).err();
+        assert_eq!(err, Some(ParseError::new(2, "empty node label")));
+    }
+
+    #[test]
+    fn matrix_backend() {
+        let (m, _): (mtx::Graph<String, mtx::Directed, mtx::Unweighted>, _) =
+            read_adjacency_list("0,1,2\n2,0", &TextOptions::csv(), 1).unwrap();
+        assert_eq!(AdjacencyList::new(&m).delimiter(',').to_string(), "0,1,2\n1\n2,0\n");
+    }
+}
@@ -0,0 +1,373 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use super::error::ParseError;
+
+/// Type of an attribute, as declared by GraphML keys and GEXF
+/// attribute classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttrType {
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    String,
+}
+
+impl AttrType {
+    /// Name of the type in GraphML's `attr.type`.
+    pub(crate) fn graphml(self) -> &'static str {
+        match self {
+            AttrType::Boolean => "boolean",
+            AttrType::Int => "int",
+            AttrType::Long => "long",
+            AttrType::Float => "float",
+            AttrType::Double => "double",
+            AttrType::String => "string",
+        }
+    }
+
+    /// Name of the type in GEXF's attribute `type`.
+    pub(crate) fn gexf(self) -> &'static str {
+        match self {
+            AttrType::Int => "integer",
+            other => other.graphml(),
+        }
+    }
+
+    /// Reads a type name from either format.
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "boolean" => Some(AttrType::Boolean),
+            "int" | "integer" => Some(AttrType::Int),
+            "long" => Some(AttrType::Long),
+            "float" => Some(AttrType::Float),
+            "double" => Some(AttrType::Double),
+            "string" => Some(AttrType::String),
+            _ => None,
+        }
+    }
+
+    /// Parses the text of a value of this type.
+    /// Strings are kept as they are; other values are trimmed.
+    /// Integers too large for `Int` are kept as their digits.
+    pub(crate) fn parse(self, text: &str) -> Option<AttrValue> {
+        match self {
+            AttrType::Boolean => match text.trim() {
+                "true" | "1" => Some(AttrValue::Boolean(true)),
+                "false" | "0" => Some(AttrValue::Boolean(false)),
+                _ => None,
+            },
+            AttrType::Int | AttrType::Long => {
+                let text = text.trim();
+                match text.parse() {
+                    Ok(i) => Some(AttrValue::Int(i)),
+                    Err(_) => text.parse::<u64>().ok().map(|_| AttrValue::String(text.to_string())),
+                }
+            }
+            AttrType::Float | AttrType::Double => text.trim().parse().ok().map(AttrValue::Float),
+            AttrType::String => Some(AttrValue::String(text.to_string())),
+        }
+    }
+}
+
+/// A typed attribute value. Integer types share `Int` and
+/// floating point types share `Float`; unsigned values beyond
+/// `i64::MAX` are carried as a `String` of their digits.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttrValue {
+    Boolean(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl fmt::Display for AttrValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttrValue::Boolean(b) => write!(f, "{}", b),
+            AttrValue::Int(i) => write!(f, "{}", i),
+            AttrValue::Float(x) => write!(f, "{}", x),
+            AttrValue::String(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Maps node data or edge weights to typed attributes and back,
+/// so that they survive a trip through GraphML or GEXF.
+///
+/// Primitive types map to one attribute named `value`; `()` maps
+/// to none. Structs can list a field per attribute:
+///
+/// ```
+/// use graph_stuff::io::attributes::{AttrType, AttrValue, AttributeMap};
+///
+/// struct City {
+///     name: String,
+///     population: u64,
+/// }
+///
+/// impl AttributeMap for City {
+///     fn schema() -> Vec<(&'static str, AttrType)> {
+///         vec![("name", AttrType::String), ("population", AttrType::Long)]
+///     }
+///
+///     fn to_attributes(&self) -> Vec<AttrValue> {
+///         vec![AttrValue::String(self.name.clone()), AttrValue::Int(self.population as i64)]
+///     }
+///
+///     fn from_attributes(values: Vec<Option<AttrValue>>) -> Option<Self> {
+///         let mut values = values.into_iter();
+///         Some(City {
+///             name: String::from_attributes(vec![values.next()?])?,
+///             population: u64::from_attributes(vec![values.next()?])?,
+///         })
+///     }
+/// }
+/// ```
+pub trait AttributeMap: Sized {
+    /// Name and type of every attribute, in a fixed order.
+    fn schema() -> Vec<(&'static str, AttrType)>;
+
+    /// The attributes of `self`, in schema order.
+    fn to_attributes(&self) -> Vec<AttrValue>;
+
+    /// Rebuilds a value from attributes in schema order, with
+    /// `None` for the ones a document leaves out. Returns `None`
+    /// if they do not make up a value.
+    fn from_attributes(values: Vec<Option<AttrValue>>) -> Option<Self>;
+}
+
+impl AttributeMap for () {
+    fn schema() -> Vec<(&'static str, AttrType)> {
+        Vec::new()
+    }
+
+    fn to_attributes(&self) -> Vec<AttrValue> {
+        Vec::new()
+    }
+
+    fn from_attributes(_: Vec<Option<AttrValue>>) -> Option<Self> {
+        Some(())
+    }
+}
+
+/// The single value of a primitive's attribute list.
+fn single(values: Vec<Option<AttrValue>>) -> Option<AttrValue> {
+    values.into_iter().next().flatten()
+}
+
+macro_rules! int_attribute {
+    ($attr:ident: $($t:ty),*) => {$(
+        impl AttributeMap for $t {
+            fn schema() -> Vec<(&'static str, AttrType)> {
+                vec![("value", AttrType::$attr)]
+            }
+
+            fn to_attributes(&self) -> Vec<AttrValue> {
+                vec![i64::try_from(*self).map_or_else(|_| AttrValue::String(self.to_string()), AttrValue::Int)]
+            }
+
+            fn from_attributes(values: Vec<Option<AttrValue>>) -> Option<Self> {
+                match single(values)? {
+                    AttrValue::Int(i) => <$t>::try_from(i).ok(),
+                    // Whole numbers are often written as `2.0`.
+                    AttrValue::Float(x) if x.fract() == 0.0 => <$t>::try_from(x as i64).ok(),
+                    AttrValue::String(s) => s.parse().ok(),
+                    _ => None,
+                }
+            }
+        }
+    )*};
+}
+
+int_attribute!(Int: i8, i16, i32, u8, u16);
+int_attribute!(Long: i64, isize, u32, u64, usize);
+
+macro_rules! float_attribute {
+    ($attr:ident: $t:ty) => {
+        impl AttributeMap for $t {
+            fn schema() -> Vec<(&'static str, AttrType)> {
+                vec![("value", AttrType::$attr)]
+            }
+
+            fn to_attributes(&self) -> Vec<AttrValue> {
+                vec![AttrValue::Float(*self as f64)]
+            }
+
+            fn from_attributes(values: Vec<Option<AttrValue>>) -> Option<Self> {
+                match single(values)? {
+                    AttrValue::Float(x) => Some(x as $t),
+                    AttrValue::Int(i) => Some(i as $t),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+float_attribute!(Float: f32);
+float_attribute!(Double: f64);
+
+impl AttributeMap for bool {
+    fn schema() -> Vec<(&'static str, AttrType)> {
+        vec![("value", AttrType::Boolean)]
+    }
+
+    fn to_attributes(&self) -> Vec<AttrValue> {
+        vec![AttrValue::Boolean(*self)]
+    }
+
+    fn from_attributes(values: Vec<Option<AttrValue>>) -> Option<Self> {
+        match single(values)? {
+            AttrValue::Boolean(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+impl AttributeMap for String {
+    fn schema() -> Vec<(&'static str, AttrType)> {
+        vec![("value", AttrType::String)]
+    }
+
+    fn to_attributes(&self) -> Vec<AttrValue> {
+        vec![AttrValue::String(self.clone())]
+    }
+
+    fn from_attributes(values: Vec<Option<AttrValue>>) -> Option<Self> {
+        single(values).map(|v| v.to_string())
+    }
+}
+
+/// The node or edge attributes a document declares, by key.
+#[derive(Debug, Default)]
+pub(crate) struct Declarations {
+    /// `(key, name, type, default)`
+    keys: Vec<(String, String, AttrType, Option<AttrValue>)>,
+}
+
+impl Declarations {
+    pub(crate) fn declare(
+        &mut self,
+        key: &str,
+        name: &str,
+        kind: &str,
+        default: Option<&str>,
+        line: usize,
+    ) -> Result<(), ParseError> {
+        let kind = AttrType::from_name(kind)
+            .ok_or_else(|| ParseError::new(line, format!("unknown attribute type `{}`", kind)))?;
+        let default = match default {
+            Some(text) => Some(
+                kind.parse(text)
+                    .ok_or_else(|| ParseError::new(line, format!("invalid default for `{}`", name)))?,
+            ),
+            None => None,
+        };
+        self.keys.push((key.to_string(), name.to_string(), kind, default));
+        Ok(())
+    }
+
+    /// Builds a `T` from the `(key, text)` values of one element,
+    /// matching its schema to declarations by name. `fallback` is
+    /// the text of a format's built-in attribute, such as a label
+    /// or weight, used for a single attribute nothing is given for.
+    pub(crate) fn read<T: AttributeMap>(
+        &self,
+        values: &[(&str, &str)],
+        fallback: Option<&str>,
+        line: usize,
+    ) -> Result<T, ParseError> {
+        let schema = T::schema();
+        let mut attributes = Vec::with_capacity(schema.len());
+        for &(name, kind) in schema.iter() {
+            let mut value = None;
+            if let Some((key, _, declared, default)) = self.keys.iter().find(|k| k.1 == name) {
+                value = match values.iter().find(|v| v.0 == key) {
+                    Some(&(_, text)) => Some(
+                        declared
+                            .parse(text)
+                            .ok_or_else(|| ParseError::new(line, format!("invalid value for `{}`", name)))?,
+                    ),
+                    None => default.clone(),
+                };
+            }
+            if value.is_none() && schema.len() == 1 {
+                value = fallback.and_then(|text| kind.parse(text));
+            }
+            attributes.push(value);
+        }
+        T::from_attributes(attributes).ok_or_else(|| ParseError::new(line, "attributes do not fit the data type"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// What a writer and a reader make of `value`.
+    fn round_trip<T: AttributeMap>(value: &T) -> Option<T> {
+        let texts = value.to_attributes().iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        let values = T::schema()
+            .iter()
+            .zip(&texts)
+            .map(|(&(_, kind), text)| kind.parse(text))
+            .collect();
+        T::from_attributes(values)
+    }
+
+    #[test]
+    fn parses_by_type() {
+        assert_eq!(AttrType::Boolean.parse(" 1 "), Some(AttrValue::Boolean(true)));
+        assert_eq!(AttrType::Boolean.parse("yes"), None);
+        assert_eq!(AttrType::Int.parse("-7"), Some(AttrValue::Int(-7)));
+        assert_eq!(AttrType::Long.parse("2.5"), None);
+        assert_eq!(AttrType::Double.parse("1e3"), Some(AttrValue::Float(1000.0)));
+        assert_eq!(AttrType::String.parse(" a "), Some(AttrValue::String(" a ".to_string())));
+        assert_eq!(AttrType::from_name("integer"), Some(AttrType::Int));
+        assert_eq!(AttrType::from_name("complex"), None);
+    }
+
+    #[test]
+    fn primitives_round_trip() {
+        assert_eq!(round_trip(&u64::MAX), Some(u64::MAX));
+        assert_eq!(round_trip(&usize::MAX), Some(usize::MAX));
+        assert_eq!(round_trip(&i64::MIN), Some(i64::MIN));
+        assert_eq!(round_trip(&-3i8), Some(-3));
+        assert_eq!(round_trip(&0.25f32), Some(0.25));
+        assert_eq!(round_trip(&true), Some(true));
+        assert_eq!(round_trip(&"x y".to_string()), Some("x y".to_string()));
+        assert_eq!(round_trip(&()), Some(()));
+
+        assert_eq!(u8::from_attributes(vec![Some(AttrValue::Int(256))]), None);
+        assert_eq!(u8::from_attributes(vec![Some(AttrValue::Float(2.0))]), Some(2));
+        assert_eq!(u8::from_attributes(vec![Some(AttrValue::Float(2.5))]), None);
+        assert_eq!(f64::from_attributes(vec![Some(AttrValue::Int(3))]), Some(3.0));
+        assert_eq!(bool::from_attributes(vec![None]), None);
+    }
+
+    #[test]
+    fn declarations_defaults_and_fallbacks() {
+        let mut keys = Declarations::default();
+        keys.declare("d0", "value", "long", Some("5"), 1).unwrap();
+        keys.declare("d1", "other", "string", None, 2).unwrap();
+        assert!(keys.declare("d2", "bad", "complex", None, 3).is_err());
+        assert!(keys.declare("d3", "bad", "int", Some("x"), 4).is_err());
+
+        assert_eq!(keys.read::<u32>(&[("d0", "7")], None, 9), Ok(7));
+        assert_eq!(keys.read::<u32>(&[], Some("8"), 9), Ok(5));
+        assert_eq!(keys.read::<u32>(&[("d0", "x")], None, 9), Err(ParseError::new(9, "invalid value for `value`")));
+        assert_eq!(keys.read::<()>(&[("d1", "ignored")], None, 9), Ok(()));
+
+        // Nothing declares `value` here, so the fallback is used.
+        let empty = Declarations::default();
+        assert_eq!(empty.read::<f64>(&[], Some("2.5"), 9), Ok(2.5));
+        assert_eq!(empty.read::<String>(&[], Some("label"), 9), Ok("label".to_string()));
+        assert_eq!(
+            empty.read::<bool>(&[], None, 9),
+            Err(ParseError::new(9, "attributes do not fit the data type"))
+        );
+    }
+}
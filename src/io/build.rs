@@ -0,0 +1,67 @@
+use std::hash::Hash;
+
+use crate::adjacency::EdgeType;
+use crate::list_graph::graph::Graph;
+use crate::mtx_graph;
+
+/// Graphs the readers can build, whichever backend they use.
+pub trait FromParts: Sized {
+    type Node;
+    type Weight: Copy;
+
+    /// Whether the graph type holds directed edges.
+    const DIRECTED: bool;
+
+    /// Builds a graph from its node data, by position, and
+    /// `(from, to, weight)` edges between those positions.
+    fn from_parts(nodes: Vec<Self::Node>, edges: Vec<(usize, usize, Self::Weight)>) -> Self;
+}
+
+impl<V, D, E> FromParts for Graph<V, D, E>
+where
+    D: EdgeType,
+    E: Copy,
+{
+    type Node = V;
+    type Weight = E;
+
+    const DIRECTED: bool = D::DIRECTED;
+
+    fn from_parts(nodes: Vec<V>, edges: Vec<(usize, usize, E)>) -> Self {
+        let mut graph = Self::new();
+        for data in nodes {
+            graph.add_node(data);
+        }
+        for (a, b, w) in edges {
+            graph.insert_edge(a, b, w);
+        }
+        graph
+    }
+}
+
+/// Weights are stored as matrix entries, so a zero weight edge
+/// is no edge; unweighted graphs should be read with weight 1.
+/// Node data must be unique, as the matrix graph indexes nodes
+/// by value.
+impl<T, D, W> FromParts for mtx_graph::graph::Graph<T, D, W>
+where
+    T: Hash + Eq + Clone,
+    D: Clone + EdgeType,
+    W: Clone,
+{
+    type Node = T;
+    type Weight = usize;
+
+    const DIRECTED: bool = D::DIRECTED;
+
+    fn from_parts(nodes: Vec<T>, edges: Vec<(usize, usize, usize)>) -> Self {
+        let mut graph = Self::default();
+        // Every node goes in before any edge: adding a node
+        // reshapes the matrix.
+        let ids: Vec<_> = nodes.into_iter().map(|t| graph.add_node(t)).collect();
+        for (a, b, w) in edges {
+            graph.insert_edge(ids[a], ids[b], w);
+        }
+        graph
+    }
+}
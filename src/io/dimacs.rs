@@ -0,0 +1,299 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::adjacency::{edge_list, Adjacency};
+use crate::list_graph::graph::{Directed, Graph};
+
+use super::error::ParseError;
+use super::text::TextOptions;
+
+/// Reads a DIMACS shortest path (`.gr`) file, as used by the 9th
+/// DIMACS Implementation Challenge, into a directed graph. Each
+/// `a u v w` line is an arc of weight `w`. Node data is the
+/// 1-based DIMACS id, so node `i` is at position `i - 1`.
+///
+/// ```
+/// use graph_stuff::io::dimacs::read_dimacs_sp;
+/// use graph_stuff::list_graph::graph::{Directed, Graph};
+///
+/// let text = "c a tiny road network\np sp 3 2\na 1 2 10\na 2 3 4\n";
+/// let g: Graph<usize, Directed, u32> = read_dimacs_sp(text).unwrap();
+/// assert_eq!(g.edges(1)[0].weight, 4);
+/// ```
+/// Time complexity:
+///   O(size of input)
+pub fn read_dimacs_sp<E>(input: &str) -> Result<Graph<usize, Directed, E>, ParseError>
+where
+    E: FromStr + Copy,
+{
+    let mut reader = Reader::new("sp");
+    for (line, fields) in TextOptions::default().records(input) {
+        match fields[0] {
+            "c" => {}
+            "p" | "a" => reader.line(line, &fields)?,
+            _ => return Err(ParseError::new(line, format!("unexpected `{}` line", fields[0]))),
+        }
+    }
+    reader.finish()
+}
+
+/// A maximum flow instance read by `read_dimacs_max_flow`.
+pub struct MaxFlowProblem<E> {
+    /// Arc capacities as edge weights.
+    pub graph: Graph<usize, Directed, E>,
+    /// Position of the source.
+    pub source: usize,
+    /// Position of the sink.
+    pub sink: usize,
+}
+
+/// Reads a DIMACS maximum flow file into a directed graph with
+/// arc capacities as weights, together with the source and sink
+/// named by its `n id s` and `n id t` lines, which must differ.
+/// Node data is the 1-based DIMACS id, as for `read_dimacs_sp`.
+/// Time complexity:
+///   O(size of input)
+pub fn read_dimacs_max_flow<E>(input: &str) -> Result<MaxFlowProblem<E>, ParseError>
+where
+    E: FromStr + Copy,
+{
+    let mut reader = Reader::new("max");
+    let (mut source, mut sink) = (None, None);
+    for (line, fields) in TextOptions::default().records(input) {
+        match fields[0] {
+            "c" => {}
+            "p" | "a" => reader.line(line, &fields)?,
+            "n" => {
+                if fields.len() != 3 {
+                    return Err(ParseError::new(line, "expected `n id s|t`"));
+                }
+                let node = reader.node(fields[1], line)?;
+                let (terminal, other) = match fields[2] {
+                    "s" => (&mut source, sink),
+                    "t" => (&mut sink, source),
+                    _ => return Err(ParseError::new(line, "expected `n id s|t`")),
+                };
+                if other == Some(node) {
+                    return Err(ParseError::new(line, "source and sink must differ"));
+                }
+                if terminal.replace(node).is_some() {
+                    return Err(ParseError::new(line, format!("second `{}` node", fields[2])));
+                }
+            }
+            _ => return Err(ParseError::new(line, format!("unexpected `{}` line", fields[0]))),
+        }
+    }
+    let line = reader.last;
+    let graph = reader.finish()?;
+    match (source, sink) {
+        (Some(source), Some(sink)) => Ok(MaxFlowProblem { graph, source, sink }),
+        _ => Err(ParseError::new(line, "missing source or sink")),
+    }
+}
+
+/// State shared by the DIMACS readers: the problem line and the
+/// arcs read so far.
+struct Reader<E> {
+    problem: &'static str,
+    size: Option<(usize, usize)>,
+    arcs: Vec<(usize, usize, E)>,
+    last: usize,
+}
+
+impl<E: FromStr + Copy> Reader<E> {
+    fn new(problem: &'static str) -> Self {
+        Self {
+            problem,
+            size: None,
+            arcs: Vec::new(),
+            last: 0,
+        }
+    }
+
+    /// Reads a `p` or `a` line.
+    fn line(&mut self, line: usize, fields: &[&str]) -> Result<(), ParseError> {
+        self.last = line;
+        if fields[0] == "p" {
+            if self.size.is_some() {
+                return Err(ParseError::new(line, "second problem line"));
+            }
+            let size = match fields {
+                &["p", problem, n, m] if problem == self.problem => n.parse().ok().zip(m.parse().ok()),
+                _ => None,
+            };
+            self.size = Some(size.ok_or_else(|| ParseError::new(line, format!("expected `p {} n m`", self.problem)))?);
+            return Ok(());
+        }
+
+        if fields.len() != 4 {
+            return Err(ParseError::new(line, "expected `a u v w`"));
+        }
+        let u = self.node(fields[1], line)?;
+        let v = self.node(fields[2], line)?;
+        let w = fields[3]
+            .parse()
+            .map_err(|_| ParseError::new(line, format!("invalid arc weight `{}`", fields[3])))?;
+        self.arcs.push((u, v, w));
+        Ok(())
+    }
+
+    /// Position of the node with DIMACS id `id`.
+    fn node(&self, id: &str, line: usize) -> Result<usize, ParseError> {
+        let (n, _) = self
+            .size
+            .ok_or_else(|| ParseError::new(line, "the problem line must come first"))?;
+        match id.parse::<usize>() {
+            Ok(i) if 1 <= i && i <= n => Ok(i - 1),
+            _ => Err(ParseError::new(line, format!("invalid node `{}`", id))),
+        }
+    }
+
+    fn finish(self) -> Result<Graph<usize, Directed, E>, ParseError> {
+        let (n, m) = self.size.ok_or_else(|| ParseError::new(1, "missing problem line"))?;
+        if self.arcs.len() != m {
+            return Err(ParseError::new(
+                self.last,
+                format!("problem line promises {} arcs, found {}", m, self.arcs.len()),
+            ));
+        }
+
+        let mut graph = Graph::<usize, Directed, E>::new();
+        for id in 1..=n {
+            graph.add_node(id);
+        }
+        for (u, v, w) in self.arcs {
+            graph.add_edge(u, v, w);
+        }
+        Ok(graph)
+    }
+}
+
+/// Every edge as arcs, undirected ones once in each direction,
+/// as DIMACS has no undirected form.
+fn arcs<G: Adjacency>(graph: &G) -> Vec<(usize, usize, G::Weight)> {
+    let mut arcs = edge_list(graph);
+    if !graph.is_directed() {
+        let back: Vec<_> = arcs.iter().filter(|e| e.0 != e.1).map(|&(u, v, w)| (v, u, w)).collect();
+        arcs.extend(back);
+    }
+    arcs
+}
+
+/// DIMACS shortest path (`.gr`) rendering of a graph from either
+/// backend, shown through `Display`. Node ids are positions plus
+/// one and undirected edges become a pair of arcs.
+pub struct DimacsShortestPath<'a, G: Adjacency> {
+    graph: &'a G,
+}
+
+impl<'a, G: Adjacency> DimacsShortestPath<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        Self { graph }
+    }
+}
+
+impl<G> fmt::Display for DimacsShortestPath<'_, G>
+where
+    G: Adjacency,
+    G::Weight: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arcs = arcs(self.graph);
+        writeln!(f, "p sp {} {}", self.graph.node_count(), arcs.len())?;
+        for (u, v, w) in arcs {
+            writeln!(f, "a {} {} {}", u + 1, v + 1, w)?;
+        }
+        Ok(())
+    }
+}
+
+/// DIMACS maximum flow rendering of a graph from either backend,
+/// with edge weights as capacities, shown through `Display`. Node
+/// ids are positions plus one and undirected edges become a pair
+/// of arcs.
+pub struct DimacsMaxFlow<'a, G: Adjacency> {
+    graph: &'a G,
+    source: G::NodeId,
+    sink: G::NodeId,
+}
+
+impl<'a, G: Adjacency> DimacsMaxFlow<'a, G> {
+    pub fn new(graph: &'a G, source: G::NodeId, sink: G::NodeId) -> Self {
+        Self { graph, source, sink }
+    }
+}
+
+impl<G> fmt::Display for DimacsMaxFlow<'_, G>
+where
+    G: Adjacency,
+    G::Weight: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let arcs = arcs(self.graph);
+        writeln!(f, "p max {} {}", self.graph.node_count(), arcs.len())?;
+        writeln!(f, "n {} s", self.graph.node_pos(self.source) + 1)?;
+        writeln!(f, "n {} t", self.graph.node_pos(self.sink) + 1)?;
+        for (u, v, w) in arcs {
+            writeln!(f, "a {} {} {}", u + 1, v + 1, w)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::flow::dinic::dinic;
+    use crate::list_graph::graph::Undirected;
+
+    use super::*;
+
+    #[test]
+    fn shortest_path_round_trip() {
+        let text = "c 9th DIMACS challenge style\n\
+                    p sp 4 3\n\
+                    c arcs follow\n\
+                    a 1 2 7\n\
+                    a 2 4 3\n\
+                    a 4 1 1\n";
+        let g: Graph<usize, Directed, u64> = read_dimacs_sp(text).unwrap();
+        assert_eq!(g.nodes()[3].data, 4);
+        assert_eq!(g.edges(1)[0].next, 3);
+        assert_eq!(DimacsShortestPath::new(&g).to_string(), "p sp 4 3\na 1 2 7\na 2 4 3\na 4 1 1\n");
+
+        let err = read_dimacs_sp::<u64>("p sp 2 1\na 1 3 5\n").err();
+        assert_eq!(err, Some(ParseError::new(2, "invalid node `3`")));
+        let err = read_dimacs_sp::<u64>("p sp 2 2\na 1 2 5\n").err();
+        assert_eq!(err.map(|e| e.line), Some(2));
+        assert!(read_dimacs_sp::<u64>("a 1 2 5\np sp 2 1\n").is_err());
+
+        let mut u = Graph::<(), Undirected, u64>::new();
+        u.add_node(());
+        u.add_node(());
+        u.add_edge(0, 1, 2);
+        assert_eq!(DimacsShortestPath::new(&u).to_string(), "p sp 2 2\na 1 2 2\na 2 1 2\n");
+    }
+
+    #[test]
+    fn max_flow_problem() {
+        let text = "c two paths from 1 to 4\n\
+                    p max 4 5\n\
+                    n 1 s\n\
+                    n 4 t\n\
+                    a 1 2 3\n\
+                    a 1 3 2\n\
+                    a 2 3 1\n\
+                    a 2 4 2\n\
+                    a 3 4 3\n";
+        let MaxFlowProblem { graph: g, source: s, sink: t } = read_dimacs_max_flow::<u32>(text).unwrap();
+        assert_eq!((s, t), (0, 3));
+        assert_eq!(dinic(&g, s, t).value, 5);
+        let written = DimacsMaxFlow::new(&g, s, t).to_string();
+        assert_eq!(written, text.lines().skip(1).map(|l| format!("{}\n", l)).collect::<String>());
+
+        let err = read_dimacs_max_flow::<u32>("p max 2 0\nn 1 s\n").err();
+        assert_eq!(err, Some(ParseError::new(1, "missing source or sink")));
+        assert!(read_dimacs_max_flow::<u32>("p sp 2 0\n").is_err());
+        let err = read_dimacs_max_flow::<u32>("p max 2 0\nn 1 s\nn 1 t\n").err();
+        assert_eq!(err, Some(ParseError::new(3, "source and sink must differ")));
+    }
+}
@@ -0,0 +1,714 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::adjacency::{edge_list, Adjacency, EdgeType};
+use crate::list_graph::graph::Graph;
+
+use super::error::ParseError;
+
+type NodeLabel<'a, N> = Box<dyn Fn(&N) -> String + 'a>;
+type EdgeLabel<'a, W> = Box<dyn Fn(W) -> String + 'a>;
+
+/// Graphviz DOT rendering of a graph from either backend, shown
+/// through `Display`. Directed graphs become a `digraph` with
+/// `->` edges, undirected ones a `graph` with `--`. Nodes are
+/// named by position; labels are off unless set up with the
+/// builder methods.
+///
+/// ```
+/// use graph_stuff::io::dot::Dot;
+/// use graph_stuff::list_graph::graph::{Directed, Graph};
+///
+/// let mut g = Graph::<&str, Directed, u32>::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// g.add_edge(a, b, 7);
+/// let dot = Dot::new(&g).node_display().weights().to_string();
+/// assert!(dot.contains("0 -> 1 [label=\"7\"];"));
+/// ```
+pub struct Dot<'a, G: Adjacency> {
+    graph: &'a G,
+    node_label: Option<NodeLabel<'a, G::Node>>,
+    edge_label: Option<EdgeLabel<'a, G::Weight>>,
+}
+
+impl<'a, G: Adjacency> Dot<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        Self {
+            graph,
+            node_label: None,
+            edge_label: None,
+        }
+    }
+
+    /// Labels every node with `f` applied to its data.
+    pub fn node_label<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&G::Node) -> String + 'a,
+    {
+        self.node_label = Some(Box::new(f));
+        self
+    }
+
+    /// Labels every edge with `f` applied to its weight.
+    pub fn edge_label<F>(mut self, f: F) -> Self
+    where
+        F: Fn(G::Weight) -> String + 'a,
+    {
+        self.edge_label = Some(Box::new(f));
+        self
+    }
+}
+
+impl<G> Dot<'_, G>
+where
+    G: Adjacency,
+    G::Node: fmt::Display,
+{
+    /// Labels nodes with their data's `Display` form.
+    pub fn node_display(self) -> Self {
+        self.node_label(|n| n.to_string())
+    }
+}
+
+impl<G> Dot<'_, G>
+where
+    G: Adjacency,
+    G::Weight: fmt::Display,
+{
+    /// Labels edges with their weight.
+    pub fn weights(self) -> Self {
+        self.edge_label(|w| w.to_string())
+    }
+}
+
+impl<G: Adjacency> fmt::Display for Dot<'_, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let directed = self.graph.is_directed();
+        writeln!(f, "{} {{", if directed { "digraph" } else { "graph" })?;
+        for p in 0..self.graph.node_count() {
+            match &self.node_label {
+                Some(label) => writeln!(f, "    {} [label={}];", p, quote(&label(self.graph.node_data(p))))?,
+                None => writeln!(f, "    {};", p)?,
+            }
+        }
+        let op = if directed { "->" } else { "--" };
+        for (u, v, w) in edge_list(self.graph) {
+            match &self.edge_label {
+                Some(label) => writeln!(f, "    {} {} {} [label={}];", u, op, v, quote(&label(w)))?,
+                None => writeln!(f, "    {} {} {};", u, op, v)?,
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Attributes of a DOT node or edge, after defaults from `node`
+/// and `edge` statements have been applied.
+pub type Attributes = HashMap<String, String>;
+
+/// Parses a DOT document into a `list_graph::Graph` with node
+/// names as data and edge weights read as numbers from the
+/// `weight` attribute, else a numeric `label`, else 1. Only a
+/// malformed `weight` is an error. Node data is the `label`
+/// attribute when present, otherwise the node name.
+pub fn read_dot<D: EdgeType>(input: &str) -> Result<Graph<String, D, f64>, ParseError> {
+    parse_dot(
+        input,
+        |name, attrs| attrs.get("label").cloned().unwrap_or_else(|| name.to_string()),
+        |attrs| match attrs.get("weight") {
+            Some(w) => w.trim().parse().ok(),
+            None => Some(attrs.get("label").and_then(|l| l.trim().parse().ok()).unwrap_or(1.0)),
+        },
+    )
+}
+
+/// Parses a DOT document, building node data from each node's name
+/// and attributes with `node`, and edge weights from each edge's
+/// attributes with `edge`, which may reject them with `None`.
+/// Nodes are numbered in order of first mention.
+///
+/// Supports `strict`, `graph`/`digraph`, node, edge and attribute
+/// statements, `a -> b -> c` chains, nested subgraphs (flattened,
+/// with scoped defaults), also as edge ends standing for all
+/// their nodes, ports (ignored), quoted strings and comments. The
+/// document kind must match `D`, as must the edge operators. As
+/// in Graphviz, a `strict` graph keeps one edge per pair of
+/// endpoints, merging the attributes of repeated edges into the
+/// first.
+/// Time complexity:
+///   O(size of input)
+pub fn parse_dot<V, D, E, NF, EF>(input: &str, mut node: NF, mut edge: EF) -> Result<Graph<V, D, E>, ParseError>
+where
+    D: EdgeType,
+    E: Copy,
+    NF: FnMut(&str, &Attributes) -> V,
+    EF: FnMut(&Attributes) -> Option<E>,
+{
+    let tokens = tokenize(input)?;
+    let mut parser = Parser {
+        tokens,
+        at: 0,
+        names: Vec::new(),
+        index: HashMap::new(),
+        node_attrs: Vec::new(),
+        edges: Vec::new(),
+        directed: false,
+        strict: false,
+        first_edge: HashMap::new(),
+        mentioned: Vec::new(),
+    };
+    let directed = parser.header()?;
+    if directed != D::DIRECTED {
+        let kind = if directed { "digraph" } else { "graph" };
+        return Err(ParseError::new(1, format!("unexpected {} for this graph type", kind)));
+    }
+    parser.expect(&Token::LBrace)?;
+    parser.statements(&Scope::default())?;
+    parser.expect(&Token::RBrace)?;
+    if let Some((_, line)) = parser.tokens.get(parser.at) {
+        return Err(ParseError::new(*line, "trailing input after graph"));
+    }
+
+    let mut graph = Graph::<V, D, E>::new();
+    for (name, attrs) in parser.names.iter().zip(&parser.node_attrs) {
+        graph.add_node(node(name, attrs));
+    }
+    for (a, b, attrs, line) in parser.edges {
+        let weight = edge(&attrs).ok_or_else(|| ParseError::new(line, "invalid edge weight"))?;
+        graph.insert_edge(a, b, weight);
+    }
+
+    Ok(graph)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// An ID and whether it was quoted, or an HTML string; only
+    /// bare IDs can be keywords.
+    Id(String, bool),
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Equals,
+    Semi,
+    Comma,
+    Colon,
+    Arrow,
+    Line,
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    let mut line_start = true;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        // `#` lines are preprocessor output, skipped like comments.
+        if (c == '#' && line_start) || (c == '/' && chars.get(i + 1) == Some(&'/')) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        line_start = false;
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(ParseError::new(line, "unterminated comment"));
+            }
+            i += 2;
+            continue;
+        }
+
+        let start_line = line;
+        let token = match c {
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '=' => Token::Equals,
+            ';' => Token::Semi,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            '-' if chars.get(i + 1) == Some(&'>') => {
+                i += 1;
+                Token::Arrow
+            }
+            '-' if chars.get(i + 1) == Some(&'-') => {
+                i += 1;
+                Token::Line
+            }
+            '"' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(ParseError::new(start_line, "unterminated string")),
+                        Some('"') => break,
+                        Some('\\') if chars.get(i + 1) == Some(&'"') => {
+                            s.push('"');
+                            i += 1;
+                        }
+                        Some('\\') if chars.get(i + 1) == Some(&'\\') => {
+                            s.push('\\');
+                            i += 1;
+                        }
+                        Some('\\') if chars.get(i + 1) == Some(&'n') => {
+                            s.push('\n');
+                            i += 1;
+                        }
+                        Some('\\') if chars.get(i + 1) == Some(&'\n') => {
+                            line += 1;
+                            i += 1;
+                        }
+                        Some(&ch) => {
+                            if ch == '\n' {
+                                line += 1;
+                            }
+                            s.push(ch);
+                        }
+                    }
+                    i += 1;
+                }
+                Token::Id(s, true)
+            }
+            '<' => {
+                // HTML string: keep the markup between the outer
+                // angle brackets.
+                let mut depth = 1;
+                let mut s = String::new();
+                loop {
+                    i += 1;
+                    match chars.get(i) {
+                        None => return Err(ParseError::new(start_line, "unterminated HTML string")),
+                        Some('<') => depth += 1,
+                        Some('>') => depth -= 1,
+                        Some('\n') => line += 1,
+                        _ => {}
+                    }
+                    if depth == 0 {
+                        break;
+                    }
+                    s.push(chars[i]);
+                }
+                Token::Id(s, true)
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                // Names and numerals; a leading minus only on the
+                // latter, as `--` and `->` were matched above.
+                let start = i;
+                while chars
+                    .get(i + 1)
+                    .is_some_and(|&n| n.is_alphanumeric() || n == '_' || n == '.')
+                {
+                    i += 1;
+                }
+                Token::Id(chars[start..=i].iter().collect(), false)
+            }
+            c => return Err(ParseError::new(line, format!("unexpected character {:?}", c))),
+        };
+        tokens.push((token, start_line));
+        i += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// Default attributes in effect inside a (sub)graph.
+#[derive(Debug, Clone, Default)]
+struct Scope {
+    node: Attributes,
+    edge: Attributes,
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    at: usize,
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    node_attrs: Vec<Attributes>,
+    edges: Vec<(usize, usize, Attributes, usize)>,
+    directed: bool,
+    strict: bool,
+    /// For `strict` graphs, the position in `edges` of every pair
+    /// of endpoints, ordered for undirected graphs.
+    first_edge: HashMap<(usize, usize), usize>,
+    /// Nodes mentioned in each subgraph being parsed.
+    mentioned: Vec<Vec<usize>>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.at).map(|(t, _)| t)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.at)
+            .or_else(|| self.tokens.last())
+            .map_or(1, |&(_, l)| l)
+    }
+
+    fn keyword(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token::Id(s, false)) if s.eq_ignore_ascii_case(word))
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), ParseError> {
+        if self.peek() == Some(token) {
+            self.at += 1;
+            Ok(())
+        } else {
+            Err(ParseError::new(self.line(), format!("expected {:?}", token)))
+        }
+    }
+
+    fn id(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(Token::Id(s, _)) => {
+                let s = s.clone();
+                self.at += 1;
+                Ok(s)
+            }
+            _ => Err(ParseError::new(self.line(), "expected an identifier")),
+        }
+    }
+
+    /// `[strict] (graph | digraph) [ID]`, returning whether the
+    /// graph is directed.
+    fn header(&mut self) -> Result<bool, ParseError> {
+        self.strict = self.keyword("strict");
+        if self.strict {
+            self.at += 1;
+        }
+        let directed = if self.keyword("digraph") {
+            true
+        } else if self.keyword("graph") {
+            false
+        } else {
+            return Err(ParseError::new(self.line(), "expected graph or digraph"));
+        };
+        self.at += 1;
+        if let Some(Token::Id(..)) = self.peek() {
+            self.at += 1;
+        }
+        self.directed = directed;
+        Ok(directed)
+    }
+
+    fn statements(&mut self, outer: &Scope) -> Result<(), ParseError> {
+        let mut scope = outer.clone();
+        while !matches!(self.peek(), Some(Token::RBrace) | None) {
+            self.statement(&mut scope)?;
+            if self.peek() == Some(&Token::Semi) {
+                self.at += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn statement(&mut self, scope: &mut Scope) -> Result<(), ParseError> {
+        if self.keyword("graph") {
+            self.at += 1;
+            self.attributes()?;
+            return Ok(());
+        }
+        if self.keyword("node") || self.keyword("edge") {
+            let is_node = self.keyword("node");
+            self.at += 1;
+            let attrs = self.attributes()?;
+            let target = if is_node { &mut scope.node } else { &mut scope.edge };
+            target.extend(attrs);
+            return Ok(());
+        }
+        let line = self.line();
+        let first = if self.at_subgraph() {
+            self.subgraph(scope)?
+        } else {
+            let name = self.node_id()?;
+            if self.peek() == Some(&Token::Equals) {
+                // Graph attribute `id = id`.
+                self.at += 1;
+                self.id()?;
+                return Ok(());
+            }
+            if !matches!(self.peek(), Some(Token::Arrow) | Some(Token::Line)) {
+                let attrs = self.attributes()?;
+                let v = self.node(&name, scope);
+                self.node_attrs[v].extend(attrs);
+                return Ok(());
+            }
+            vec![self.node(&name, scope)]
+        };
+
+        let mut chain = vec![(first, line)];
+        while let Some(op) = self.peek() {
+            let arrow = match op {
+                Token::Arrow => true,
+                Token::Line => false,
+                _ => break,
+            };
+            if arrow != self.directed {
+                let (op, kind) = if arrow { ("->", "graph") } else { ("--", "digraph") };
+                return Err(ParseError::new(self.line(), format!("`{}` in a {}", op, kind)));
+            }
+            self.at += 1;
+            let line = self.line();
+            chain.push((self.operand(scope)?, line));
+        }
+        let attrs = self.attributes()?;
+        for pair in chain.windows(2) {
+            for &a in &pair[0].0 {
+                for &b in &pair[1].0 {
+                    let mut edge_attrs = scope.edge.clone();
+                    edge_attrs.extend(attrs.clone());
+                    self.edge(a, b, edge_attrs, pair[1].1);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn at_subgraph(&self) -> bool {
+        self.keyword("subgraph") || self.peek() == Some(&Token::LBrace)
+    }
+
+    /// `[subgraph [ID]] { statements }`, returning the nodes it
+    /// mentions, in order, so that it can be an edge operand.
+    fn subgraph(&mut self, scope: &Scope) -> Result<Vec<usize>, ParseError> {
+        if self.keyword("subgraph") {
+            self.at += 1;
+            if let Some(Token::Id(..)) = self.peek() {
+                self.at += 1;
+            }
+        }
+        self.expect(&Token::LBrace)?;
+        self.mentioned.push(Vec::new());
+        self.statements(scope)?;
+        self.expect(&Token::RBrace)?;
+        let mut nodes = self.mentioned.pop().unwrap_or_default();
+        let mut seen = HashSet::new();
+        nodes.retain(|&v| seen.insert(v));
+        if let Some(outer) = self.mentioned.last_mut() {
+            outer.extend(&nodes);
+        }
+        Ok(nodes)
+    }
+
+    /// One side of an edge: a node or all nodes of a subgraph.
+    fn operand(&mut self, scope: &Scope) -> Result<Vec<usize>, ParseError> {
+        if self.at_subgraph() {
+            return self.subgraph(scope);
+        }
+        let name = self.node_id()?;
+        Ok(vec![self.node(&name, scope)])
+    }
+
+    /// Records an edge, or in a `strict` graph merges a repeated
+    /// one into the first.
+    fn edge(&mut self, a: usize, b: usize, attrs: Attributes, line: usize) {
+        if self.strict {
+            let key = if self.directed || a <= b { (a, b) } else { (b, a) };
+            if let Some(&i) = self.first_edge.get(&key) {
+                self.edges[i].2.extend(attrs);
+                return;
+            }
+            self.first_edge.insert(key, self.edges.len());
+        }
+        self.edges.push((a, b, attrs, line));
+    }
+
+    /// A node name, dropping any `:port[:compass]` suffix.
+    fn node_id(&mut self) -> Result<String, ParseError> {
+        let name = self.id()?;
+        while self.peek() == Some(&Token::Colon) {
+            self.at += 1;
+            self.id()?;
+        }
+        Ok(name)
+    }
+
+    fn node(&mut self, name: &str, scope: &Scope) -> usize {
+        let v = match self.index.get(name) {
+            Some(&v) => v,
+            None => {
+                let v = self.names.len();
+                self.names.push(name.to_string());
+                self.index.insert(name.to_string(), v);
+                self.node_attrs.push(scope.node.clone());
+                v
+            }
+        };
+        if let Some(nodes) = self.mentioned.last_mut() {
+            nodes.push(v);
+        }
+        v
+    }
+
+    /// Zero or more `[a = b, c = d; e]` lists.
+    fn attributes(&mut self) -> Result<Attributes, ParseError> {
+        let mut attrs = Attributes::new();
+        while self.peek() == Some(&Token::LBracket) {
+            self.at += 1;
+            while self.peek() != Some(&Token::RBracket) {
+                let key = self.id()?;
+                let value = if self.peek() == Some(&Token::Equals) {
+                    self.at += 1;
+                    self.id()?
+                } else {
+                    "true".to_string()
+                };
+                attrs.insert(key, value);
+                if let Some(Token::Comma) | Some(Token::Semi) = self.peek() {
+                    self.at += 1;
+                }
+            }
+            self.at += 1;
+        }
+        Ok(attrs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Undirected};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn writes_both_backends() {
+        let mut g = Graph::<&str, Undirected, u32>::new();
+        let a = g.add_node("a");
+        let b = g.add_node("say \"hi\"");
+        g.add_edge(a, b, 4);
+        g.add_edge(b, b, 1);
+        let dot = Dot::new(&g).node_display().weights().to_string();
+        assert_eq!(
+            dot,
+            "graph {\n    0 [label=\"a\"];\n    1 [label=\"say \\\"hi\\\"\"];\n    0 -- 1 [label=\"4\"];\n    1 -- 1 [label=\"1\"];\n}\n"
+        );
+
+        let mut m = mtx::Graph::<char, mtx::Directed, mtx::Weighted>::default();
+        let x = m.add_node('x');
+        let y = m.add_node('y');
+        m.add_edge(x, y, 2);
+        let dot = Dot::new(&m).edge_label(|w| format!("w={}", w)).to_string();
+        assert_eq!(dot, "digraph {\n    0;\n    1;\n    0 -> 1 [label=\"w=2\"];\n}\n");
+    }
+
+    #[test]
+    fn parses_documents() {
+        let input = r#"
+            /* a small network */
+            strict digraph "net" {
+                rankdir = LR;
+                node [shape=box]
+                a [label="Start"];
+                a -> b -> c [weight=2.5]
+                subgraph cluster_0 {
+                    edge [label=7, note=<<b>x</b>>]
+                    c -> "d e":port:n
+                }
+                // comment
+                b -> a
+            }
+        "#;
+        let g: Graph<String, Directed, f64> = read_dot(input).unwrap();
+        let names: Vec<&str> = g.nodes().iter().map(|n| n.data.as_str()).collect();
+        assert_eq!(names, vec!["Start", "b", "c", "d e"]);
+        let edges: Vec<(usize, usize, f64)> = (0..4)
+            .flat_map(|u| g.edges(u).iter().map(move |e| (u, e.next, e.weight)))
+            .collect();
+        assert_eq!(edges, vec![(0, 1, 2.5), (1, 2, 2.5), (1, 0, 1.0), (2, 3, 7.0)]);
+
+        assert!(read_dot::<Undirected>(input).is_err());
+        let err = read_dot::<Directed>("digraph {\n a -> b [weight=x]\n}").err();
+        assert_eq!(err, Some(ParseError::new(2, "invalid edge weight")));
+        let g: Graph<String, Directed, f64> = read_dot("digraph { a -> b [label=\"x\"] }").unwrap();
+        assert_eq!(g.edges(0)[0].weight, 1.0);
+        let g: Graph<String, Directed, f64> = read_dot("digraph { \"node\" -> \"edge\"; \"graph\" }").unwrap();
+        let names: Vec<&str> = g.nodes().iter().map(|n| n.data.as_str()).collect();
+        assert_eq!(names, vec!["node", "edge", "graph"]);
+        let err = read_dot::<Directed>("digraph {\n a -> \n}").err();
+        assert_eq!(err.map(|e| e.line), Some(3));
+    }
+
+    #[test]
+    fn subgraph_edge_ends() {
+        // `a` is mentioned in `s` too, so it also links to `g`.
+        let input = "digraph { a -> { b c } -> d; subgraph s { e; f -> a } -> g }";
+        let g: Graph<String, Directed, f64> = read_dot(input).unwrap();
+        let names: Vec<&str> = g.nodes().iter().map(|n| n.data.as_str()).collect();
+        assert_eq!(names, vec!["a", "b", "c", "d", "e", "f", "g"]);
+        let edges: Vec<(usize, usize)> = (0..g.len())
+            .flat_map(|u| g.edges(u).iter().map(move |e| (u, e.next)))
+            .collect();
+        assert_eq!(edges, vec![(0, 1), (0, 2), (0, 6), (1, 3), (2, 3), (4, 6), (5, 0), (5, 6)]);
+
+        let err = read_dot::<Undirected>("graph {\n a -> b\n}").err();
+        assert_eq!(err, Some(ParseError::new(2, "`->` in a graph")));
+        assert!(read_dot::<Directed>("digraph { a -- b }").is_err());
+    }
+
+    #[test]
+    fn strict_merges_repeated_edges() {
+        let input = "strict graph { a -- b; b -- a [weight=3]; a -- a; a -- a; b -- c }";
+        let g: Graph<String, Undirected, f64> = read_dot(input).unwrap();
+        let edges: Vec<(usize, f64)> = g.edges(0).iter().map(|e| (e.next, e.weight)).collect();
+        assert_eq!(edges, vec![(1, 3.0), (0, 1.0), (0, 1.0)]);
+        assert_eq!(g.edges(1).len(), 2);
+
+        let g: Graph<String, Directed, f64> = read_dot("strict digraph { a -> b; b -> a; a -> b }").unwrap();
+        assert_eq!((g.edges(0).len(), g.edges(1).len()), (1, 1));
+        let g: Graph<String, Directed, f64> = read_dot("digraph { a -> b; a -> b }").unwrap();
+        assert_eq!(g.edges(0).len(), 2);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut g = Graph::<String, Undirected, f64>::new();
+        for name in ["p", "q", "r"] {
+            g.add_node(name.to_string());
+        }
+        g.add_edge(0, 1, 1.5);
+        g.add_edge(1, 2, 3.0);
+        let text = Dot::new(&g).node_display().weights().to_string();
+        let back: Graph<String, Undirected, f64> = read_dot(&text).unwrap();
+        assert_eq!(Dot::new(&back).node_display().weights().to_string(), text);
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::adjacency::{edge_list, Adjacency};
+
+use super::build::FromParts;
+use super::error::ParseError;
+use super::text::{plain_field, Labels, TextOptions};
+
+type NodeLabel<'a, N> = Box<dyn Fn(&N) -> String + 'a>;
+type EdgeLabel<'a, W> = Box<dyn Fn(W) -> String + 'a>;
+
+/// Reads an edge list, one `u v [w]` edge per line, into either
+/// backend. `weight` turns the optional third field into an edge
+/// weight, rejecting it with `None`. Returns the graph together
+/// with the position of every node label. Each line is one edge,
+/// so an undirected edge should be listed once.
+///
+/// ```
+/// use graph_stuff::io::edge_list::read_edge_list;
+/// use graph_stuff::io::text::TextOptions;
+/// use graph_stuff::list_graph::graph::{Directed, Graph};
+///
+/// let text = "# flights\nAMS,LHR,45\nLHR,JFK,420\n";
+/// let (g, index): (Graph<String, Directed, u32>, _) =
+///     read_edge_list(text, &TextOptions::csv(), |w| w?.parse().ok()).unwrap();
+/// assert_eq!(g.edges(index["LHR"])[0].weight, 420);
+/// ```
+/// Time complexity:
+///   O(size of input)
+pub fn read_edge_list<G, F>(
+    input: &str,
+    options: &TextOptions,
+    mut weight: F,
+) -> Result<(G, HashMap<String, usize>), ParseError>
+where
+    G: FromParts,
+    G::Node: From<String>,
+    F: FnMut(Option<&str>) -> Option<G::Weight>,
+{
+    let mut labels = Labels::new(options);
+    let mut edges = Vec::new();
+    for (line, fields) in options.records(input) {
+        if fields.len() < 2 || fields.len() > 3 {
+            return Err(ParseError::new(line, "expected `u v [w]`"));
+        }
+        let a = labels.get(fields[0], line)?;
+        let b = labels.get(fields[1], line)?;
+        let w = weight(fields.get(2).copied()).ok_or_else(|| ParseError::new(line, "invalid edge weight"))?;
+        edges.push((a, b, w));
+    }
+
+    Ok((G::from_parts(labels.names.into_iter().map(G::Node::from).collect(), edges), labels.index))
+}
+
+/// Edge list rendering of a graph from either backend, one
+/// `u v [w]` line per edge and undirected edges listed once.
+/// Nodes are written as positions unless labelled. A label or
+/// weight that would not read back as one field, e.g. because it
+/// contains the delimiter, makes formatting fail with `fmt::Error`.
+pub struct EdgeList<'a, G: Adjacency> {
+    graph: &'a G,
+    delimiter: char,
+    node_label: Option<NodeLabel<'a, G::Node>>,
+    weight: Option<EdgeLabel<'a, G::Weight>>,
+}
+
+impl<'a, G: Adjacency> EdgeList<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        Self {
+            graph,
+            delimiter: ' ',
+            node_label: None,
+            weight: None,
+        }
+    }
+
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Names every node with `f` applied to its data.
+    pub fn node_label<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&G::Node) -> String + 'a,
+    {
+        self.node_label = Some(Box::new(f));
+        self
+    }
+
+    /// Adds a weight column, formatted with `f`.
+    pub fn weight<F>(mut self, f: F) -> Self
+    where
+        F: Fn(G::Weight) -> String + 'a,
+    {
+        self.weight = Some(Box::new(f));
+        self
+    }
+}
+
+impl<G: Adjacency> fmt::Display for EdgeList<'_, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |p: usize| match &self.node_label {
+            Some(label) => label(self.graph.node_data(p)),
+            None => p.to_string(),
+        };
+        let d = self.delimiter;
+        for (u, v, w) in edge_list(self.graph) {
+            write!(f, "{}{}{}", plain_field(name(u), d)?, d, plain_field(name(v), d)?)?;
+            if let Some(weight) = &self.weight {
+                write!(f, "{}{}", d, plain_field(weight(w), d)?)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Write;
+
+    use crate::list_graph::graph::{Directed, Graph, Undirected};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn read_both_backends() {
+        let text = "a b 2\n\n# comment line\nb c 5 # trailing\nc a\n";
+        let weight = |w: Option<&str>| w.map_or(Some(1), |w| w.parse().ok());
+        let (g, index): (Graph<String, Undirected, u32>, _) = read_edge_list(text, &TextOptions::default(), weight).unwrap();
+        assert_eq!(index["c"], 2);
+        let edges: Vec<(usize, u32)> = g.edges(index["a"]).iter().map(|e| (e.next, e.weight)).collect();
+        assert_eq!(edges, vec![(1, 2), (2, 1)]);
+
+        let (m, _): (mtx::Graph<String, mtx::Directed, mtx::Weighted>, _) =
+            read_edge_list(text, &TextOptions::default(), |w| w.map_or(Some(1), |w| w.parse().ok())).unwrap();
+        let b = m.get_idx(&"b".to_string()).unwrap();
+        let c = m.get_idx(&"c".to_string()).unwrap();
+        assert_eq!(m.edge_weight(b, c), 5);
+        assert!(!m.has_edge(c, b));
+
+        let bad = read_edge_list::<Graph<String, Directed, u32>, _>("a b x", &TextOptions::default(), |w| w?.parse().ok());
+        assert_eq!(bad.err(), Some(ParseError::new(1, "invalid edge weight")));
+    }
+
+    #[test]
+    fn indices_and_round_trip() {
+        let options = TextOptions {
+            indices: true,
+            ..TextOptions::csv()
+        };
+        let (g, _): (Graph<String, Directed, f64>, _) =
+            read_edge_list("0, 3, 1.5\n3, 1, 2", &options, |w| w?.parse().ok()).unwrap();
+        assert_eq!(g.len(), 4);
+        assert_eq!(g.edges(3)[0].next, 1);
+        let huge = read_edge_list::<Graph<String, Directed, f64>, _>("0, 1000000000000", &options, |_| Some(1.0));
+        assert_eq!(huge.err(), Some(ParseError::new(1, "node index 1000000000000 is above 1048576")));
+
+        let text = EdgeList::new(&g).delimiter(',').weight(|w| w.to_string()).to_string();
+        assert_eq!(text, "0,3,1.5\n3,1,2\n");
+        let (back, _): (Graph<String, Directed, f64>, _) = read_edge_list(&text, &options, |w| w?.parse().ok()).unwrap();
+        assert_eq!(EdgeList::new(&back).delimiter(',').weight(|w| w.to_string()).to_string(), text);
+    }
+
+    #[test]
+    fn rejects_ambiguous_labels() {
+        let mut g = Graph::<&str, Directed, u32>::new();
+        let a = g.add_node("a b");
+        let c = g.add_node("c");
+        g.add_edge(a, c, 1);
+        let mut out = String::new();
+        assert!(write!(out, "{}", EdgeList::new(&g).node_label(|n| n.to_string())).is_err());
+        let csv = EdgeList::new(&g).delimiter(',').node_label(|n| n.to_string()).to_string();
+        assert_eq!(csv, "a b,c\n");
+        for label in ["", " c", "c#1", "c,d", "c\nd"] {
+            let csv = EdgeList::new(&g).delimiter(',').node_label(move |_| label.to_string());
+            assert!(write!(out, "{}", csv).is_err());
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::adjacency::{edge_list, Adjacency};
+
+use super::attributes::{AttrType, AttrValue, AttributeMap, Declarations};
+use super::build::FromParts;
+use super::error::ParseError;
+use super::graphml::direction_error;
+use super::xml::{self, escape};
+
+/// GEXF 1.3 rendering of a graph from either backend, shown
+/// through `Display`, as read by Gephi. Node data and edge weights
+/// are written as `<attvalue>`s of the attributes in their
+/// `AttributeMap` schema. Data that is a single string also
+/// becomes the node `label`, and a single number the edge
+/// `weight`, so that Gephi shows them without further setup.
+/// Nodes and edges are identified by position.
+pub struct Gexf<'a, G: Adjacency> {
+    graph: &'a G,
+}
+
+impl<'a, G: Adjacency> Gexf<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        Self { graph }
+    }
+}
+
+impl<G> fmt::Display for Gexf<'_, G>
+where
+    G: Adjacency,
+    G::Node: AttributeMap,
+    G::Weight: AttributeMap,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(f, "<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">")?;
+        let direction = if self.graph.is_directed() { "directed" } else { "undirected" };
+        writeln!(f, "  <graph mode=\"static\" defaultedgetype=\"{}\">", direction)?;
+        let node_schema = G::Node::schema();
+        let edge_schema = G::Weight::schema();
+        for (class, schema) in [("node", &node_schema), ("edge", &edge_schema)] {
+            if schema.is_empty() {
+                continue;
+            }
+            writeln!(f, "    <attributes class=\"{}\">", class)?;
+            for (i, (title, kind)) in schema.iter().enumerate() {
+                writeln!(
+                    f,
+                    "      <attribute id=\"{}\" title=\"{}\" type=\"{}\"/>",
+                    i,
+                    escape(title),
+                    kind.gexf()
+                )?;
+            }
+            writeln!(f, "    </attributes>")?;
+        }
+
+        writeln!(f, "    <nodes>")?;
+        for p in 0..self.graph.node_count() {
+            let values = self.graph.node_data(p).to_attributes();
+            let label = match (node_schema.as_slice(), values.first()) {
+                ([(_, AttrType::String)], Some(value)) => value.to_string(),
+                _ => p.to_string(),
+            };
+            write!(f, "      <node id=\"{}\" label=\"{}\"", p, escape(&label))?;
+            write_attvalues(f, &values, "node")?;
+        }
+        writeln!(f, "    </nodes>")?;
+
+        writeln!(f, "    <edges>")?;
+        for (i, (u, v, w)) in edge_list(self.graph).into_iter().enumerate() {
+            let values = w.to_attributes();
+            write!(f, "      <edge id=\"{}\" source=\"{}\" target=\"{}\"", i, u, v)?;
+            if let ([_], [value @ AttrValue::Int(_)]) | ([_], [value @ AttrValue::Float(_)]) =
+                (edge_schema.as_slice(), values.as_slice())
+            {
+                write!(f, " weight=\"{}\"", value)?;
+            }
+            write_attvalues(f, &values, "edge")?;
+        }
+        writeln!(f, "    </edges>")?;
+        writeln!(f, "  </graph>")?;
+        writeln!(f, "</gexf>")
+    }
+}
+
+/// Finishes an element opened up to its attributes with its
+/// `<attvalues>`, attribute ids counting up from 0.
+fn write_attvalues(f: &mut fmt::Formatter<'_>, values: &[AttrValue], tag: &str) -> fmt::Result {
+    if values.is_empty() {
+        return writeln!(f, "/>");
+    }
+    write!(f, "><attvalues>")?;
+    for (i, value) in values.iter().enumerate() {
+        write!(f, "<attvalue for=\"{}\" value=\"{}\"/>", i, escape(&value.to_string()))?;
+    }
+    writeln!(f, "</attvalues></{}>", tag)
+}
+
+/// Reads a GEXF document, such as a Gephi export, into either
+/// backend. Node data and edge weights are rebuilt from the
+/// `<attvalue>`s of attributes titled after their `AttributeMap`
+/// schema, falling back to the attribute default. Data with a
+/// single attribute that is not given falls back to the node
+/// `label` or the edge `weight`, which defaults to 1. The
+/// `defaultedgetype` must match the graph type, with `mutual`
+/// edges read as undirected; dynamic data is ignored.
+/// Time complexity:
+///   O(size of input)
+pub fn read_gexf<G>(input: &str) -> Result<G, ParseError>
+where
+    G: FromParts,
+    G::Node: AttributeMap,
+    G::Weight: AttributeMap,
+{
+    let root = xml::parse(input)?;
+    if root.name != "gexf" {
+        return Err(ParseError::new(root.line, "expected a <gexf> document"));
+    }
+    let graph = root
+        .child("graph")
+        .ok_or_else(|| ParseError::new(root.line, "no <graph> element"))?;
+    let directed = |kind: &str| kind == "directed";
+    if directed(graph.attr("defaultedgetype").unwrap_or("undirected")) != G::DIRECTED {
+        return Err(ParseError::new(graph.line, direction_error(G::DIRECTED)));
+    }
+
+    let mut node_attributes = Declarations::default();
+    let mut edge_attributes = Declarations::default();
+    for class in graph.children("attributes") {
+        let declarations = match class.require("class")? {
+            "node" => &mut node_attributes,
+            "edge" => &mut edge_attributes,
+            _ => continue,
+        };
+        for attribute in class.children("attribute") {
+            let id = attribute.require("id")?;
+            let default = attribute.child("default").map(|d| d.text.as_str());
+            declarations.declare(
+                id,
+                attribute.attr("title").unwrap_or(id),
+                attribute.attr("type").unwrap_or("string"),
+                default,
+                attribute.line,
+            )?;
+        }
+    }
+
+    let mut index = HashMap::new();
+    let mut nodes = Vec::new();
+    for node in graph.child("nodes").into_iter().flat_map(|n| n.children("node")) {
+        if index.insert(node.require("id")?, nodes.len()).is_some() {
+            return Err(ParseError::new(node.line, "duplicate node id"));
+        }
+        nodes.push(node_attributes.read(&attvalues(node)?, node.attr("label"), node.line)?);
+    }
+
+    let mut edges = Vec::new();
+    for edge in graph.child("edges").into_iter().flat_map(|e| e.children("edge")) {
+        let end = |name: &str| -> Result<usize, ParseError> {
+            let id = edge.require(name)?;
+            index
+                .get(id)
+                .copied()
+                .ok_or_else(|| ParseError::new(edge.line, format!("unknown node `{}`", id)))
+        };
+        let (a, b) = (end("source")?, end("target")?);
+        if edge.attr("type").is_some_and(|kind| directed(kind) != G::DIRECTED) {
+            return Err(ParseError::new(edge.line, direction_error(G::DIRECTED)));
+        }
+        let weight = Some(edge.attr("weight").unwrap_or("1"));
+        edges.push((a, b, edge_attributes.read(&attvalues(edge)?, weight, edge.line)?));
+    }
+
+    Ok(G::from_parts(nodes, edges))
+}
+
+/// The `(attribute id, value)` pairs of an element's `<attvalue>`s.
+fn attvalues(element: &xml::Element) -> Result<Vec<(&str, &str)>, ParseError> {
+    element
+        .child("attvalues")
+        .into_iter()
+        .flat_map(|a| a.children("attvalue"))
+        // GEXF 1.1 names the attribute with `id` rather than `for`.
+        .map(|a| Ok((a.attr("for").map_or_else(|| a.require("id"), Ok)?, a.require("value")?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph, Undirected};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn round_trip_both_backends() {
+        let mut g = Graph::<String, Directed, f64>::new();
+        let a = g.add_node("<a>".to_string());
+        let b = g.add_node("b".to_string());
+        g.add_edge(a, b, 0.25);
+        g.add_edge(b, a, 3.0);
+        let text = Gexf::new(&g).to_string();
+        assert_eq!(
+            text,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n\
+             \x20 <graph mode=\"static\" defaultedgetype=\"directed\">\n\
+             \x20   <attributes class=\"node\">\n\
+             \x20     <attribute id=\"0\" title=\"value\" type=\"string\"/>\n\
+             \x20   </attributes>\n\
+             \x20   <attributes class=\"edge\">\n\
+             \x20     <attribute id=\"0\" title=\"value\" type=\"double\"/>\n\
+             \x20   </attributes>\n\
+             \x20   <nodes>\n\
+             \x20     <node id=\"0\" label=\"&lt;a&gt;\"><attvalues><attvalue for=\"0\" value=\"&lt;a&gt;\"/></attvalues></node>\n\
+             \x20     <node id=\"1\" label=\"b\"><attvalues><attvalue for=\"0\" value=\"b\"/></attvalues></node>\n\
+             \x20   </nodes>\n\
+             \x20   <edges>\n\
+             \x20     <edge id=\"0\" source=\"0\" target=\"1\" weight=\"0.25\"><attvalues><attvalue for=\"0\" value=\"0.25\"/></attvalues></edge>\n\
+             \x20     <edge id=\"1\" source=\"1\" target=\"0\" weight=\"3\"><attvalues><attvalue for=\"0\" value=\"3\"/></attvalues></edge>\n\
+             \x20   </edges>\n\
+             \x20 </graph>\n\
+             </gexf>\n"
+        );
+        let back: Graph<String, Directed, f64> = read_gexf(&text).unwrap();
+        assert_eq!(Gexf::new(&back).to_string(), text);
+
+        let m: mtx::Graph<String, mtx::Directed, mtx::Weighted> = read_gexf(&text.replace("0.25", "2")).unwrap();
+        let x = m.get_idx(&"<a>".to_string()).unwrap();
+        let y = m.get_idx(&"b".to_string()).unwrap();
+        assert_eq!((m.edge_weight(x, y), m.edge_weight(y, x)), (2, 3));
+        let text = Gexf::new(&m).to_string();
+        assert!(text.contains("<edge id=\"1\" source=\"1\" target=\"0\" weight=\"3\">"));
+    }
+
+    #[test]
+    fn reads_gephi_exports() {
+        // Labels and native weights only, plus a typed attribute
+        // the data type has no use for.
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <gexf xmlns="http://www.gexf.net/1.2draft" version="1.2">
+              <meta><creator>Gephi 0.10</creator></meta>
+              <graph mode="static">
+                <attributes class="node">
+                  <attribute id="modularity_class" title="Modularity Class" type="integer"/>
+                </attributes>
+                <nodes>
+                  <node id="n0" label="Myriel"><attvalues><attvalue for="modularity_class" value="1"/></attvalues></node>
+                  <node id="n1" label="Napoleon"/>
+                </nodes>
+                <edges>
+                  <edge id="e0" source="n1" target="n0" weight="2.0"/>
+                  <edge id="e1" source="n0" target="n0"/>
+                </edges>
+              </graph>
+            </gexf>"#;
+        let g: Graph<String, Undirected, f64> = read_gexf(input).unwrap();
+        let names: Vec<&str> = g.nodes().iter().map(|n| n.data.as_str()).collect();
+        assert_eq!(names, vec!["Myriel", "Napoleon"]);
+        let edges: Vec<(usize, f64)> = g.edges(0).iter().map(|e| (e.next, e.weight)).collect();
+        assert_eq!(edges, vec![(1, 2.0), (0, 1.0), (0, 1.0)]);
+
+        assert!(read_gexf::<Graph<String, Directed, f64>>(input).is_err());
+        let err = read_gexf::<Graph<String, Undirected, f64>>(&input.replace("weight=\"2.0\"", "weight=\"x\"")).err();
+        assert_eq!(err, Some(ParseError::new(13, "attributes do not fit the data type")));
+    }
+}
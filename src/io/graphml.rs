@@ -0,0 +1,280 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::adjacency::{edge_list, Adjacency};
+
+use super::attributes::{AttrValue, AttributeMap, Declarations};
+use super::build::FromParts;
+use super::error::ParseError;
+use super::xml::{self, escape};
+
+/// GraphML rendering of a graph from either backend, shown
+/// through `Display`, as read by yEd and most other tools. Node
+/// data and edge weights are written as `<data>` under keys from
+/// their `AttributeMap` schema; nodes are named `n` followed by
+/// their position.
+///
+/// ```
+/// use graph_stuff::io::graphml::{read_graphml, GraphMl};
+/// use graph_stuff::list_graph::graph::{Directed, Graph};
+///
+/// let mut g = Graph::<String, Directed, f64>::new();
+/// let a = g.add_node("a".to_string());
+/// let b = g.add_node("b".to_string());
+/// g.add_edge(a, b, 2.5);
+/// let text = GraphMl::new(&g).to_string();
+/// assert!(text.contains("<edge source=\"n0\" target=\"n1\"><data key=\"d1\">2.5</data></edge>"));
+///
+/// let back: Graph<String, Directed, f64> = read_graphml(&text).unwrap();
+/// assert_eq!(back.edges(0)[0].weight, 2.5);
+/// ```
+pub struct GraphMl<'a, G: Adjacency> {
+    graph: &'a G,
+}
+
+impl<'a, G: Adjacency> GraphMl<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        Self { graph }
+    }
+}
+
+impl<G> fmt::Display for GraphMl<'_, G>
+where
+    G: Adjacency,
+    G::Node: AttributeMap,
+    G::Weight: AttributeMap,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(f, "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">")?;
+        let node_schema = G::Node::schema();
+        let edge_schema = G::Weight::schema();
+        let keys = node_schema.iter().map(|a| ("node", a)).chain(edge_schema.iter().map(|a| ("edge", a)));
+        for (i, (domain, (name, kind))) in keys.enumerate() {
+            writeln!(
+                f,
+                "  <key id=\"d{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>",
+                i,
+                domain,
+                escape(name),
+                kind.graphml()
+            )?;
+        }
+
+        let direction = if self.graph.is_directed() { "directed" } else { "undirected" };
+        writeln!(f, "  <graph id=\"G\" edgedefault=\"{}\">", direction)?;
+        for p in 0..self.graph.node_count() {
+            let values = self.graph.node_data(p).to_attributes();
+            write!(f, "    <node id=\"n{}\"", p)?;
+            write_data(f, &values, 0, "node")?;
+        }
+        for (u, v, w) in edge_list(self.graph) {
+            write!(f, "    <edge source=\"n{}\" target=\"n{}\"", u, v)?;
+            write_data(f, &w.to_attributes(), node_schema.len(), "edge")?;
+        }
+        writeln!(f, "  </graph>")?;
+        writeln!(f, "</graphml>")
+    }
+}
+
+/// Finishes an element opened up to its attributes with one
+/// `<data>` child per value, keys counting up from `first`.
+fn write_data(f: &mut fmt::Formatter<'_>, values: &[AttrValue], first: usize, tag: &str) -> fmt::Result {
+    if values.is_empty() {
+        return writeln!(f, "/>");
+    }
+    write!(f, ">")?;
+    for (i, value) in values.iter().enumerate() {
+        write!(f, "<data key=\"d{}\">{}</data>", first + i, escape(&value.to_string()))?;
+    }
+    writeln!(f, "</{}>", tag)
+}
+
+/// Reads a GraphML document into either backend, rebuilding node
+/// data and edge weights from the `<data>` of keys named after
+/// their `AttributeMap` schema. Missing values fall back to the
+/// key's default. Only the first `<graph>` is read, nested
+/// graphs and hyperedges are ignored, and its `edgedefault` must
+/// match the graph type.
+/// Time complexity:
+///   O(size of input)
+pub fn read_graphml<G>(input: &str) -> Result<G, ParseError>
+where
+    G: FromParts,
+    G::Node: AttributeMap,
+    G::Weight: AttributeMap,
+{
+    let root = xml::parse(input)?;
+    if root.name != "graphml" {
+        return Err(ParseError::new(root.line, "expected a <graphml> document"));
+    }
+
+    let mut node_keys = Declarations::default();
+    let mut edge_keys = Declarations::default();
+    for key in root.children("key") {
+        let default = key.child("default").map(|d| d.text.as_str());
+        let id = key.require("id")?;
+        let name = key.attr("attr.name").unwrap_or(id);
+        let kind = key.attr("attr.type").unwrap_or("string");
+        match key.attr("for").unwrap_or("all") {
+            "node" => node_keys.declare(id, name, kind, default, key.line)?,
+            "edge" => edge_keys.declare(id, name, kind, default, key.line)?,
+            "all" => {
+                node_keys.declare(id, name, kind, default, key.line)?;
+                edge_keys.declare(id, name, kind, default, key.line)?;
+            }
+            _ => {}
+        }
+    }
+
+    let graph = root
+        .child("graph")
+        .ok_or_else(|| ParseError::new(root.line, "no <graph> element"))?;
+    let directed = graph.require("edgedefault")? == "directed";
+    if directed != G::DIRECTED {
+        return Err(ParseError::new(graph.line, direction_error(G::DIRECTED)));
+    }
+
+    let mut index = HashMap::new();
+    let mut nodes = Vec::new();
+    for node in graph.children("node") {
+        if index.insert(node.require("id")?, nodes.len()).is_some() {
+            return Err(ParseError::new(node.line, "duplicate node id"));
+        }
+        nodes.push(node_keys.read(&data(node)?, None, node.line)?);
+    }
+
+    let mut edges = Vec::new();
+    for edge in graph.children("edge") {
+        let end = |name: &str| -> Result<usize, ParseError> {
+            let id = edge.require(name)?;
+            index
+                .get(id)
+                .copied()
+                .ok_or_else(|| ParseError::new(edge.line, format!("unknown node `{}`", id)))
+        };
+        let (a, b) = (end("source")?, end("target")?);
+        if edge.attr("directed").is_some_and(|d| (d == "true") != G::DIRECTED) {
+            return Err(ParseError::new(edge.line, direction_error(G::DIRECTED)));
+        }
+        edges.push((a, b, edge_keys.read(&data(edge)?, None, edge.line)?));
+    }
+
+    Ok(G::from_parts(nodes, edges))
+}
+
+pub(crate) fn direction_error(directed: bool) -> &'static str {
+    if directed {
+        "expected directed edges"
+    } else {
+        "expected undirected edges"
+    }
+}
+
+/// The `(key, text)` pairs of an element's `<data>` children.
+fn data(element: &xml::Element) -> Result<Vec<(&str, &str)>, ParseError> {
+    element
+        .children("data")
+        .map(|d| Ok((d.require("key")?, d.text.as_str())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::attributes::AttrType;
+    use crate::list_graph::graph::{Directed, Graph, Undirected};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Station {
+        name: String,
+        zone: i32,
+    }
+
+    impl AttributeMap for Station {
+        fn schema() -> Vec<(&'static str, AttrType)> {
+            vec![("name", AttrType::String), ("zone", AttrType::Int)]
+        }
+
+        fn to_attributes(&self) -> Vec<AttrValue> {
+            vec![AttrValue::String(self.name.clone()), AttrValue::Int(self.zone as i64)]
+        }
+
+        fn from_attributes(values: Vec<Option<AttrValue>>) -> Option<Self> {
+            let mut values = values.into_iter();
+            Some(Station {
+                name: String::from_attributes(vec![values.next()?])?,
+                zone: i32::from_attributes(vec![values.next()?])?,
+            })
+        }
+    }
+
+    #[test]
+    fn round_trip_struct_data() {
+        let mut g = Graph::<Station, Undirected, f64>::new();
+        let a = g.add_node(Station {
+            name: "Bank & Monument".to_string(),
+            zone: 1,
+        });
+        let b = g.add_node(Station {
+            name: "Stratford".to_string(),
+            zone: 2,
+        });
+        g.add_edge(a, b, 7.5);
+        g.add_edge(b, b, 0.5);
+        let text = GraphMl::new(&g).to_string();
+        assert_eq!(
+            text,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n\
+             \x20 <key id=\"d0\" for=\"node\" attr.name=\"name\" attr.type=\"string\"/>\n\
+             \x20 <key id=\"d1\" for=\"node\" attr.name=\"zone\" attr.type=\"int\"/>\n\
+             \x20 <key id=\"d2\" for=\"edge\" attr.name=\"value\" attr.type=\"double\"/>\n\
+             \x20 <graph id=\"G\" edgedefault=\"undirected\">\n\
+             \x20   <node id=\"n0\"><data key=\"d0\">Bank &amp; Monument</data><data key=\"d1\">1</data></node>\n\
+             \x20   <node id=\"n1\"><data key=\"d0\">Stratford</data><data key=\"d1\">2</data></node>\n\
+             \x20   <edge source=\"n0\" target=\"n1\"><data key=\"d2\">7.5</data></edge>\n\
+             \x20   <edge source=\"n1\" target=\"n1\"><data key=\"d2\">0.5</data></edge>\n\
+             \x20 </graph>\n\
+             </graphml>\n"
+        );
+        let back: Graph<Station, Undirected, f64> = read_graphml(&text).unwrap();
+        assert_eq!(back.nodes()[a].data, g.nodes()[a].data);
+        assert_eq!(GraphMl::new(&back).to_string(), text);
+        assert!(read_graphml::<Graph<Station, Directed, f64>>(&text).is_err());
+
+        let mut big = Graph::<u64, Directed, ()>::new();
+        big.add_node(u64::MAX);
+        let back: Graph<u64, Directed, ()> = read_graphml(&GraphMl::new(&big).to_string()).unwrap();
+        assert_eq!(back.nodes()[0].data, u64::MAX);
+    }
+
+    #[test]
+    fn reads_foreign_documents() {
+        // Keys out of order, a default, a `for="all"` key, unused
+        // keys and yEd style extras.
+        let input = r#"<?xml version="1.0"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:y="http://www.yworks.com/xml/graphml">
+              <key id="w" for="edge" attr.name="value" attr.type="long"><default>1</default></key>
+              <key id="gfx" for="node" yfiles.type="nodegraphics"/>
+              <key id="lbl" for="all" attr.name="value" attr.type="string"/>
+              <graph id="G" edgedefault="directed">
+                <node id="x"><data key="gfx"><y:ShapeNode/></data><data key="lbl">X</data></node>
+                <node id="y"><data key="lbl">Y</data></node>
+                <edge source="x" target="y"><data key="w">4</data></edge>
+                <edge source="y" target="x"/>
+              </graph>
+            </graphml>"#;
+        let m: mtx::Graph<String, mtx::Directed, mtx::Weighted> = read_graphml(input).unwrap();
+        let x = m.get_idx(&"X".to_string()).unwrap();
+        let y = m.get_idx(&"Y".to_string()).unwrap();
+        assert_eq!(m.edge_weight(x, y), 4);
+        assert_eq!(m.edge_weight(y, x), 1);
+
+        let bad = input.replace("target=\"y\"", "target=\"z\"");
+        let err = read_graphml::<Graph<(), Directed, u64>>(&bad).err();
+        assert_eq!(err.map(|e| e.line), Some(9));
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+
+use crate::adjacency::{edge_list, Adjacency};
+
+use super::build::FromParts;
+use super::error::ParseError;
+use super::text::TextOptions;
+
+type EdgeLabel<'a, W> = Box<dyn Fn(W) -> String + 'a>;
+
+/// The value type of a Matrix Market file's entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    /// No values; every entry is an edge.
+    Pattern,
+    Integer,
+    Real,
+}
+
+impl Field {
+    fn name(self) -> &'static str {
+        match self {
+            Field::Pattern => "pattern",
+            Field::Integer => "integer",
+            Field::Real => "real",
+        }
+    }
+}
+
+/// Reads a Matrix Market coordinate file, such as those of the
+/// SuiteSparse collection, as the adjacency matrix of a graph in
+/// either backend. Entry `i j` is an edge from node `i` to node
+/// `j`, and node data is the 1-based row number, so the node of
+/// row `i` is at position `i - 1`. `weight` turns an entry's value
+/// into an edge weight, rejecting it with `None`; it is passed
+/// `None` for `pattern` files.
+///
+/// `symmetric` files list the lower triangle only and can be read
+/// into either kind of graph, with each entry mirrored in a
+/// directed one. `general` files need a directed graph. Other
+/// symmetries, `complex` values and the dense `array` format are
+/// not supported.
+///
+/// ```
+/// use graph_stuff::io::matrix_market::read_matrix_market;
+/// use graph_stuff::mtx_graph::graph::{Graph, Undirected, Weighted};
+///
+/// let text = "%%MatrixMarket matrix coordinate integer symmetric\n3 3 2\n2 1 5\n3 2 7\n";
+/// let g: Graph<usize, Undirected, Weighted> = read_matrix_market(text, |w| w?.parse().ok()).unwrap();
+/// let (a, b) = (g.get_idx(&1).unwrap(), g.get_idx(&2).unwrap());
+/// assert_eq!((g.edge_weight(a, b), g.edge_weight(b, a)), (5, 5));
+/// ```
+/// Time complexity:
+///   O(size of input)
+pub fn read_matrix_market<G, F>(input: &str, mut weight: F) -> Result<G, ParseError>
+where
+    G: FromParts,
+    G::Node: From<usize>,
+    F: FnMut(Option<&str>) -> Option<G::Weight>,
+{
+    let header: Vec<String> = input
+        .lines()
+        .next()
+        .unwrap_or("")
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect();
+    let header: Vec<&str> = header.iter().map(String::as_str).collect();
+    let (field, symmetric) = match header.as_slice() {
+        ["%%matrixmarket", "matrix", "coordinate", field, symmetry] => {
+            let field = match *field {
+                "pattern" => Field::Pattern,
+                "integer" => Field::Integer,
+                "real" => Field::Real,
+                other => return Err(ParseError::new(1, format!("unsupported field `{}`", other))),
+            };
+            let symmetric = match *symmetry {
+                "general" => false,
+                "symmetric" => true,
+                other => return Err(ParseError::new(1, format!("unsupported symmetry `{}`", other))),
+            };
+            (field, symmetric)
+        }
+        _ => return Err(ParseError::new(1, "expected `%%MatrixMarket matrix coordinate <field> <symmetry>`")),
+    };
+    if !symmetric && !G::DIRECTED {
+        return Err(ParseError::new(1, "a general matrix needs a directed graph"));
+    }
+
+    let options = TextOptions {
+        comment: Some("%".to_string()),
+        ..TextOptions::default()
+    };
+    let mut records = options.records(input);
+    let (line, size) = records
+        .next()
+        .ok_or_else(|| ParseError::new(1, "missing size line"))?;
+    let size: Vec<usize> = size
+        .iter()
+        .map(|s| s.parse())
+        .collect::<Result<_, _>>()
+        .map_err(|_| ParseError::new(line, "invalid size line"))?;
+    let (n, entries) = match *size.as_slice() {
+        [rows, cols, entries] if rows == cols => (rows, entries),
+        [_, _, _] => return Err(ParseError::new(line, "an adjacency matrix must be square")),
+        _ => return Err(ParseError::new(line, "expected `rows cols entries`")),
+    };
+
+    let values = if field == Field::Pattern { 0 } else { 1 };
+    let mut edges = Vec::new();
+    let mut count = 0;
+    for (line, fields) in records {
+        if fields.len() != 2 + values {
+            return Err(ParseError::new(line, "wrong number of fields in entry"));
+        }
+        let index = |s: &str| match s.parse::<usize>() {
+            Ok(i) if 1 <= i && i <= n => Ok(i - 1),
+            _ => Err(ParseError::new(line, format!("invalid index `{}`", s))),
+        };
+        let (i, j) = (index(fields[0])?, index(fields[1])?);
+        if symmetric && i < j {
+            return Err(ParseError::new(line, "symmetric entries must be in the lower triangle"));
+        }
+        let w = weight(fields.get(2).copied()).ok_or_else(|| ParseError::new(line, "invalid entry value"))?;
+        edges.push((i, j, w));
+        if symmetric && G::DIRECTED && i != j {
+            edges.push((j, i, w));
+        }
+        count += 1;
+    }
+    if count != entries {
+        return Err(ParseError::new(
+            line,
+            format!("size line promises {} entries, found {}", entries, count),
+        ));
+    }
+
+    Ok(G::from_parts((1..=n).map(G::Node::from).collect(), edges))
+}
+
+/// Matrix Market coordinate rendering of the adjacency matrix of a
+/// graph from either backend, shown through `Display`. Undirected
+/// graphs are written as `symmetric`, listing the lower triangle;
+/// directed ones as `general`. Rows and columns are node positions
+/// plus one. Entries are `pattern` unless `weights` is set.
+pub struct MatrixMarket<'a, G: Adjacency> {
+    graph: &'a G,
+    weight: Option<(Field, EdgeLabel<'a, G::Weight>)>,
+}
+
+impl<'a, G: Adjacency> MatrixMarket<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        Self { graph, weight: None }
+    }
+
+    /// Writes entry values of type `field`, formatted with `f`.
+    pub fn weights<F>(mut self, field: Field, f: F) -> Self
+    where
+        F: Fn(G::Weight) -> String + 'a,
+    {
+        self.weight = match field {
+            Field::Pattern => None,
+            _ => Some((field, Box::new(f))),
+        };
+        self
+    }
+}
+
+impl<G: Adjacency> fmt::Display for MatrixMarket<'_, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = self.weight.as_ref().map_or(Field::Pattern, |(field, _)| *field);
+        let directed = self.graph.is_directed();
+        let symmetry = if directed { "general" } else { "symmetric" };
+        writeln!(f, "%%MatrixMarket matrix coordinate {} {}", field.name(), symmetry)?;
+        let edges = edge_list(self.graph);
+        let n = self.graph.node_count();
+        writeln!(f, "{} {} {}", n, n, edges.len())?;
+        for (u, v, w) in edges {
+            // `edge_list` gives undirected edges with `u <= v`,
+            // which is the upper triangle.
+            let (row, col) = if directed { (u, v) } else { (v, u) };
+            write!(f, "{} {}", row + 1, col + 1)?;
+            if let Some((_, weight)) = &self.weight {
+                write!(f, " {}", weight(w))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph, Undirected};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn reads_symmetric_into_both_kinds() {
+        let text = "%%MatrixMarket matrix coordinate pattern symmetric\n\
+                    % a triangle with a loop\n\
+                    3 3 4\n\
+                    2 1\n3 1\n3 2\n3 3\n";
+        let g: Graph<usize, Undirected, ()> = read_matrix_market(text, |_| Some(())).unwrap();
+        let degrees: Vec<usize> = (0..3).map(|p| g.edges(p).len()).collect();
+        assert_eq!(degrees, vec![2, 2, 4]);
+        let d: Graph<usize, Directed, ()> = read_matrix_market(text, |_| Some(())).unwrap();
+        let degrees: Vec<usize> = (0..3).map(|p| d.edges(p).len()).collect();
+        assert_eq!(degrees, vec![2, 2, 3]);
+
+        let general = text.replace("symmetric", "general");
+        assert!(read_matrix_market::<Graph<usize, Undirected, ()>, _>(&general, |_| Some(())).is_err());
+        let short = text.replace("3 3 4", "3 3 5");
+        let err = read_matrix_market::<Graph<usize, Directed, ()>, _>(&short, |_| Some(())).err();
+        assert_eq!(err, Some(ParseError::new(3, "size line promises 5 entries, found 4")));
+        let huge = text.replace("3 3 4", "3 3 18446744073709551615");
+        let err = read_matrix_market::<Graph<usize, Directed, ()>, _>(&huge, |_| Some(())).err();
+        assert_eq!(err.map(|e| e.line), Some(3));
+        let upper = text.replace("3 2\n", "2 3\n");
+        let err = read_matrix_market::<Graph<usize, Directed, ()>, _>(&upper, |_| Some(())).err();
+        assert_eq!(err.map(|e| e.line), Some(6));
+    }
+
+    #[test]
+    fn round_trip_both_backends() {
+        let mut m = mtx::Graph::<usize, mtx::Directed, mtx::Weighted>::default();
+        let a = m.add_node(1);
+        let b = m.add_node(2);
+        let c = m.add_node(3);
+        m.add_edge(a, b, 4);
+        m.add_edge(c, a, 9);
+        let text = MatrixMarket::new(&m).weights(Field::Integer, |w| w.to_string()).to_string();
+        assert_eq!(text, "%%MatrixMarket matrix coordinate integer general\n3 3 2\n1 2 4\n3 1 9\n");
+        let back: mtx::Graph<usize, mtx::Directed, mtx::Weighted> =
+            read_matrix_market(&text, |w| w?.parse().ok()).unwrap();
+        assert_eq!(MatrixMarket::new(&back).weights(Field::Integer, |w| w.to_string()).to_string(), text);
+
+        let mut g = Graph::<usize, Undirected, f64>::new();
+        for i in 1..=3 {
+            g.add_node(i);
+        }
+        g.add_edge(0, 2, 0.5);
+        g.add_edge(1, 1, 2.0);
+        let text = MatrixMarket::new(&g).weights(Field::Real, |w| w.to_string()).to_string();
+        assert_eq!(text, "%%MatrixMarket matrix coordinate real symmetric\n3 3 2\n3 1 0.5\n2 2 2\n");
+        let back: Graph<usize, Undirected, f64> = read_matrix_market(&text, |w| w?.parse().ok()).unwrap();
+        assert_eq!(MatrixMarket::new(&back).weights(Field::Real, |w| w.to_string()).to_string(), text);
+    }
+}
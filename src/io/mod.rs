@@ -0,0 +1,15 @@
+//! Reading and writing graphs in common text formats. Writers
+//! render through `fmt::Display` or into any `io::Write`; readers
+//! parse a whole document from a string.
+pub mod adjacency_list;
+pub mod attributes;
+pub mod build;
+pub mod dimacs;
+pub mod dot;
+pub mod edge_list;
+pub mod error;
+pub mod gexf;
+pub mod graphml;
+pub mod matrix_market;
+pub mod text;
+mod xml;
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use super::error::ParseError;
+
+/// How line based text formats are split up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextOptions {
+    /// Field separator; `None` splits on runs of whitespace.
+    /// Fields are trimmed either way, so `Some(',')` reads CSV.
+    pub delimiter: Option<char>,
+    /// Text from this marker to the end of a line is ignored.
+    pub comment: Option<String>,
+    /// Read nodes as numeric indices rather than labels, so
+    /// that node `i` ends up at position `i`. Nodes up to the
+    /// largest index are created even if never mentioned.
+    pub indices: bool,
+    /// With `indices`, the largest index accepted, so that a stray
+    /// huge number is an error rather than millions of nodes.
+    pub max_index: usize,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: None,
+            comment: Some("#".to_string()),
+            indices: false,
+            max_index: 1 << 20,
+        }
+    }
+}
+
+impl TextOptions {
+    /// Comma separated fields.
+    pub fn csv() -> Self {
+        Self {
+            delimiter: Some(','),
+            ..Self::default()
+        }
+    }
+
+    /// The non-empty lines of `input` split into fields, with
+    /// their 1-based line numbers.
+    pub(crate) fn records<'s>(&'s self, input: &'s str) -> impl Iterator<Item = (usize, Vec<&'s str>)> + 's {
+        input.lines().enumerate().filter_map(move |(i, line)| {
+            let line = match &self.comment {
+                Some(marker) => line.split(marker.as_str()).next().unwrap_or(""),
+                None => line,
+            };
+            let fields: Vec<&str> = match self.delimiter {
+                Some(d) => line.split(d).map(str::trim).collect(),
+                None => line.split_whitespace().collect(),
+            };
+            if fields.iter().all(|f| f.is_empty()) {
+                None
+            } else {
+                Some((i + 1, fields))
+            }
+        })
+    }
+}
+
+/// Checks that a written field reads back as itself from a line
+/// split on `delimiter` with the default `#` comments: it must be
+/// non-empty and trimmed, without line breaks, comment markers or
+/// the delimiter (any whitespace, if that is the delimiter).
+pub(crate) fn plain_field(field: String, delimiter: char) -> Result<String, fmt::Error> {
+    let separator = |c: char| c == delimiter || c == '\n' || c == '\r' || (delimiter.is_whitespace() && c.is_whitespace());
+    if field.is_empty() || field.trim() != field || field.contains('#') || field.contains(separator) {
+        return Err(fmt::Error);
+    }
+    Ok(field)
+}
+
+/// Interns node labels as positions, much like `node_map` does
+/// for the matrix backend.
+#[derive(Debug, Default)]
+pub(crate) struct Labels {
+    pub(crate) index: HashMap<String, usize>,
+    pub(crate) names: Vec<String>,
+    /// The largest index accepted, when reading indices.
+    indices: Option<usize>,
+}
+
+impl Labels {
+    pub(crate) fn new(options: &TextOptions) -> Self {
+        Self {
+            indices: if options.indices { Some(options.max_index) } else { None },
+            ..Self::default()
+        }
+    }
+
+    /// Position of the node named `label`, creating it if needed.
+    /// Empty labels, as from `,b` in CSV, are an error.
+    pub(crate) fn get(&mut self, label: &str, line: usize) -> Result<usize, ParseError> {
+        if label.is_empty() {
+            return Err(ParseError::new(line, "empty node label"));
+        }
+        if let Some(max) = self.indices {
+            let i: usize = label
+                .parse()
+                .map_err(|_| ParseError::new(line, format!("invalid node index {:?}", label)))?;
+            if i > max {
+                return Err(ParseError::new(line, format!("node index {} is above {}", i, max)));
+            }
+            while self.names.len() <= i {
+                let name = self.names.len().to_string();
+                self.index.insert(name.clone(), self.names.len());
+                self.names.push(name);
+            }
+            return Ok(i);
+        }
+        if let Some(&p) = self.index.get(label) {
+            return Ok(p);
+        }
+        let p = self.names.len();
+        self.index.insert(label.to_string(), p);
+        self.names.push(label.to_string());
+        Ok(p)
+    }
+}
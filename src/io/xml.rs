@@ -0,0 +1,250 @@
+use super::error::ParseError;
+
+/// An element read by `parse`, which handles just enough XML for
+/// GraphML and GEXF: elements, attributes, text, comments, CDATA
+/// and character references. Namespaces are dropped, so elements
+/// and attributes go by local name.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) children: Vec<Element>,
+    pub(crate) text: String,
+    pub(crate) line: usize,
+}
+
+impl Element {
+    pub(crate) fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+
+    /// Like `attr`, but a missing attribute is an error.
+    pub(crate) fn require(&self, name: &str) -> Result<&str, ParseError> {
+        self.attr(name)
+            .ok_or_else(|| ParseError::new(self.line, format!("<{}> needs a `{}` attribute", self.name, name)))
+    }
+
+    pub(crate) fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    pub(crate) fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|c| c.name == name)
+    }
+}
+
+/// Parses a document into its root element.
+pub(crate) fn parse(input: &str) -> Result<Element, ParseError> {
+    let mut reader = Reader { input, at: 0, line: 1 };
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+    while reader.at < input.len() {
+        let rest = &input[reader.at..];
+        if rest.starts_with("<!--") {
+            reader.skip_past("-->")?;
+        } else if rest.starts_with("<![CDATA[") {
+            reader.advance("<![CDATA[".len());
+            let text = reader.skip_past("]]>")?;
+            if let Some(open) = stack.last_mut() {
+                open.text.push_str(text);
+            }
+        } else if rest.starts_with("<?") {
+            reader.skip_past("?>")?;
+        } else if rest.starts_with("<!") {
+            reader.skip_past(">")?;
+        } else if rest.starts_with("</") {
+            let line = reader.line;
+            reader.advance(2);
+            let name = local(reader.skip_past(">")?.trim());
+            let closed = match stack.pop() {
+                Some(open) if open.name == name => open,
+                _ => return Err(ParseError::new(line, format!("unexpected </{}>", name))),
+            };
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(closed),
+                None => root = Some(closed),
+            }
+        } else if rest.starts_with('<') {
+            let (element, closed) = reader.start_tag()?;
+            if root.is_some() {
+                return Err(ParseError::new(element.line, "content after the root element"));
+            }
+            if !closed {
+                stack.push(element);
+            } else {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => root = Some(element),
+                }
+            }
+        } else {
+            let line = reader.line;
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = unescape(&rest[..end], line)?;
+            reader.advance(end);
+            match stack.last_mut() {
+                Some(open) => open.text.push_str(&text),
+                None if text.trim().is_empty() => {}
+                None => return Err(ParseError::new(line, "text outside the root element")),
+            }
+        }
+    }
+
+    if let Some(open) = stack.last() {
+        return Err(ParseError::new(reader.line, format!("<{}> is never closed", open.name)));
+    }
+    root.ok_or_else(|| ParseError::new(reader.line, "no root element"))
+}
+
+/// Escapes text for use in element content and quoted attributes.
+pub(crate) fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn local(name: &str) -> String {
+    name.rsplit(':').next().unwrap_or(name).to_string()
+}
+
+fn unescape(s: &str, line: usize) -> Result<String, ParseError> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let end = rest[amp..]
+            .find(';')
+            .ok_or_else(|| ParseError::new(line, "unterminated character reference"))?;
+        let entity = &rest[amp + 1..amp + end];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ if entity.starts_with("#x") => u32::from_str_radix(&entity[2..], 16).ok().and_then(char::from_u32),
+            _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+        out.push(c.ok_or_else(|| ParseError::new(line, format!("unknown entity &{};", entity)))?);
+        rest = &rest[amp + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+struct Reader<'s> {
+    input: &'s str,
+    at: usize,
+    line: usize,
+}
+
+impl<'s> Reader<'s> {
+    fn advance(&mut self, n: usize) {
+        self.line += self.input[self.at..self.at + n].matches('\n').count();
+        self.at += n;
+    }
+
+    /// Moves past the next `marker`, returning the text before it.
+    fn skip_past(&mut self, marker: &str) -> Result<&'s str, ParseError> {
+        let rest = &self.input[self.at..];
+        let end = rest
+            .find(marker)
+            .ok_or_else(|| ParseError::new(self.line, format!("missing `{}`", marker)))?;
+        self.advance(end + marker.len());
+        Ok(&rest[..end])
+    }
+
+    /// Reads `<name attr="value" ...>`, and whether it closes itself.
+    fn start_tag(&mut self) -> Result<(Element, bool), ParseError> {
+        let line = self.line;
+        let rest = &self.input[self.at..];
+        // `>` may appear inside quoted values, so track quotes.
+        let mut quote = None;
+        let end = rest
+            .char_indices()
+            .find(|&(_, c)| match quote {
+                Some(q) => {
+                    if c == q {
+                        quote = None;
+                    }
+                    false
+                }
+                None => {
+                    if c == '"' || c == '\'' {
+                        quote = Some(c);
+                    }
+                    c == '>'
+                }
+            })
+            .map(|(i, _)| i)
+            .ok_or_else(|| ParseError::new(line, "unterminated tag"))?;
+        let mut tag = &rest[1..end];
+        let closed = tag.ends_with('/');
+        if closed {
+            tag = &tag[..tag.len() - 1];
+        }
+        self.advance(end + 1);
+
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let mut element = Element {
+            name: local(&tag[..name_end]),
+            line,
+            ..Element::default()
+        };
+        let mut attrs = tag[name_end..].trim_start();
+        while !attrs.is_empty() {
+            let eq = attrs.find('=').ok_or_else(|| ParseError::new(line, "expected `=` in attribute"))?;
+            let name = attrs[..eq].trim();
+            let value = attrs[eq + 1..].trim_start();
+            let q = value
+                .chars()
+                .next()
+                .filter(|&c| c == '"' || c == '\'')
+                .ok_or_else(|| ParseError::new(line, "attribute value must be quoted"))?;
+            let close = value[1..]
+                .find(q)
+                .ok_or_else(|| ParseError::new(line, "unterminated attribute value"))?;
+            // Namespace declarations are of no interest.
+            if name != "xmlns" && !name.starts_with("xmlns:") {
+                element.attributes.push((local(name), unescape(&value[1..close + 1], line)?));
+            }
+            attrs = value[close + 2..].trim_start();
+        }
+        Ok((element, closed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_elements() {
+        let doc = "<?xml version=\"1.0\"?>\n<!-- c -->\n<a xmlns:y=\"urn:y\" k='1 > 0'>\n  <y:b v=\"&lt;&#65;&amp;\"/>\n  <c>x<![CDATA[<y>]]>&#x7a;</c>\n</a>\n";
+        let root = parse(doc).unwrap();
+        assert_eq!(root.name, "a");
+        assert_eq!(root.attr("k"), Some("1 > 0"));
+        assert_eq!(root.child("b").and_then(|b| b.attr("v")), Some("<A&"));
+        let c = root.child("c").unwrap();
+        assert_eq!((c.text.as_str(), c.line), ("x<y>z", 5));
+        assert_eq!(escape("<\"a\" & 'b'>"), "&lt;&quot;a&quot; &amp; &apos;b&apos;&gt;");
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        assert_eq!(parse("<a>\n<b></a>").err().map(|e| e.line), Some(2));
+        assert!(parse("<a>").is_err());
+        assert!(parse("<a/><b/>").is_err());
+        assert!(parse("<a x=1/>").is_err());
+    }
+}
@@ -0,0 +1,3 @@
+//! Structural matching between graphs: isomorphism, induced
+//! subgraph isomorphism and monomorphism.
+pub mod vf2;
@@ -0,0 +1,452 @@
+use std::collections::HashMap;
+
+use crate::adjacency::Adjacency;
+
+/// The relation a VF2 search looks for between the first
+/// graph (the pattern) and the second (the target).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Problem {
+    /// A bijection preserving edges and non-edges.
+    Isomorphism,
+    /// An injection whose image is an induced subgraph of
+    /// the target: edges and non-edges are both preserved.
+    InducedSubgraph,
+    /// An injection preserving edges only; the target may
+    /// hold extra edges between mapped nodes.
+    Monomorphism,
+}
+
+type NodeMatch<'a, A, B> = Box<dyn Fn(&A, &B) -> bool + 'a>;
+type EdgeMatch<'a, A, B> = Box<dyn Fn(A, B) -> bool + 'a>;
+
+/// VF2 matcher between a pattern graph and a target graph,
+/// which may come from different backends. Nodes are taken
+/// in a VF2++ style order: each next pattern node is the one
+/// with the most already ordered neighbours, ties going to
+/// the higher degree, so the search stays connected and
+/// fails early.
+///
+/// Node and edge compatibility default to "always" and can
+/// be narrowed with `node_match` and `edge_match`, which
+/// compare node data and edge weights.
+pub struct Vf2<'a, G1: Adjacency, G2: Adjacency> {
+    g1: &'a G1,
+    g2: &'a G2,
+    node_match: Option<NodeMatch<'a, G1::Node, G2::Node>>,
+    edge_match: Option<EdgeMatch<'a, G1::Weight, G2::Weight>>,
+}
+
+impl<'a, G1: Adjacency, G2: Adjacency> Vf2<'a, G1, G2> {
+    pub fn new(pattern: &'a G1, target: &'a G2) -> Self {
+        Self {
+            g1: pattern,
+            g2: target,
+            node_match: None,
+            edge_match: None,
+        }
+    }
+
+    /// Only maps nodes whose data satisfies `f`.
+    pub fn node_match<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&G1::Node, &G2::Node) -> bool + 'a,
+    {
+        self.node_match = Some(Box::new(f));
+        self
+    }
+
+    /// Only maps edges whose weights satisfy `f`.
+    pub fn edge_match<F>(mut self, f: F) -> Self
+    where
+        F: Fn(G1::Weight, G2::Weight) -> bool + 'a,
+    {
+        self.edge_match = Some(Box::new(f));
+        self
+    }
+
+    pub fn is_isomorphic(&self) -> bool {
+        self.find(Problem::Isomorphism).is_some()
+    }
+
+    pub fn is_subgraph_isomorphic(&self) -> bool {
+        self.find(Problem::InducedSubgraph).is_some()
+    }
+
+    pub fn is_monomorphic(&self) -> bool {
+        self.find(Problem::Monomorphism).is_some()
+    }
+
+    /// Finds one mapping for `problem` as `(pattern node,
+    /// target node)` pairs in pattern order.
+    /// Time complexity:
+    ///   exponential in the worst case.
+    pub fn find(&self, problem: Problem) -> Option<Vec<(G1::NodeId, G2::NodeId)>> {
+        self.search(problem, 1).pop()
+    }
+
+    /// Finds every mapping for `problem`. Automorphisms of
+    /// the pattern show up as distinct mappings.
+    /// Time complexity:
+    ///   exponential in the worst case.
+    pub fn find_all(&self, problem: Problem) -> Vec<Vec<(G1::NodeId, G2::NodeId)>> {
+        self.search(problem, usize::MAX)
+    }
+
+    fn search(&self, problem: Problem, limit: usize) -> Vec<Vec<(G1::NodeId, G2::NodeId)>> {
+        let a = Side::new(self.g1);
+        let b = Side::new(self.g2);
+        let (n1, n2) = (a.len(), b.len());
+        if self.g1.is_directed() != self.g2.is_directed() || n1 > n2 {
+            return Vec::new();
+        }
+        if problem == Problem::Isomorphism && (n1 != n2 || a.edges() != b.edges()) {
+            return Vec::new();
+        }
+
+        let mut state = State {
+            vf2: self,
+            order: a.order(),
+            a,
+            b,
+            problem,
+            core1: vec![None; n1],
+            core2: vec![None; n2],
+            term1: vec![0; n1],
+            term2: vec![0; n2],
+            limit,
+            found: Vec::new(),
+        };
+        state.extend(0);
+
+        state
+            .found
+            .into_iter()
+            .map(|m| {
+                m.into_iter()
+                    .map(|(u, v)| (self.g1.node_id(u), self.g2.node_id(v)))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Sorted, deduplicated successor and predecessor lists of
+/// one graph, with the weight of the first edge per pair.
+struct Side<W> {
+    succ: Vec<Vec<usize>>,
+    pred: Vec<Vec<usize>>,
+    weights: HashMap<(usize, usize), W>,
+}
+
+impl<W: Copy> Side<W> {
+    fn new<G: Adjacency<Weight = W>>(graph: &G) -> Self {
+        let n = graph.node_count();
+        let mut succ = vec![Vec::new(); n];
+        let mut pred = vec![Vec::new(); n];
+        let mut weights = HashMap::new();
+        for (u, (v, w)) in (0..n).flat_map(|u| graph.out_edges(u).into_iter().map(move |e| (u, e))) {
+            weights.entry((u, v)).or_insert(w);
+            succ[u].push(v);
+            pred[v].push(u);
+        }
+        for list in succ.iter_mut().chain(pred.iter_mut()) {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        Self { succ, pred, weights }
+    }
+
+    fn len(&self) -> usize {
+        self.succ.len()
+    }
+
+    fn edges(&self) -> usize {
+        self.succ.iter().map(Vec::len).sum()
+    }
+
+    fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.succ[u].binary_search(&v).is_ok()
+    }
+
+    fn degree(&self, u: usize) -> usize {
+        self.succ[u].len() + self.pred[u].len()
+    }
+
+    fn neighbors(&self, u: usize) -> impl Iterator<Item = usize> + '_ {
+        self.succ[u].iter().chain(&self.pred[u]).copied()
+    }
+
+    /// Greedy matching order: repeatedly takes the node with
+    /// the most ordered neighbours, then the highest degree.
+    fn order(&self) -> Vec<usize> {
+        let n = self.len();
+        let mut placed = vec![false; n];
+        let mut links = vec![0usize; n];
+        let mut order = Vec::with_capacity(n);
+        for _ in 0..n {
+            let u = (0..n)
+                .filter(|&u| !placed[u])
+                .max_by_key(|&u| (links[u], self.degree(u), std::cmp::Reverse(u)))
+                .unwrap();
+            placed[u] = true;
+            order.push(u);
+            for v in self.neighbors(u) {
+                links[v] += 1;
+            }
+        }
+        order
+    }
+}
+
+struct State<'s, 'a, G1: Adjacency, G2: Adjacency> {
+    vf2: &'s Vf2<'a, G1, G2>,
+    a: Side<G1::Weight>,
+    b: Side<G2::Weight>,
+    problem: Problem,
+    order: Vec<usize>,
+    core1: Vec<Option<usize>>,
+    core2: Vec<Option<usize>>,
+    /// Number of mapped neighbours of each node; nonzero
+    /// marks the terminal set of the VF2 state.
+    term1: Vec<usize>,
+    term2: Vec<usize>,
+    limit: usize,
+    found: Vec<Vec<(usize, usize)>>,
+}
+
+impl<G1: Adjacency, G2: Adjacency> State<'_, '_, G1, G2> {
+    fn extend(&mut self, depth: usize) {
+        if self.found.len() >= self.limit {
+            return;
+        }
+        if depth == self.order.len() {
+            let mapping = self
+                .core1
+                .iter()
+                .enumerate()
+                .map(|(u, v)| (u, v.unwrap()))
+                .collect();
+            self.found.push(mapping);
+            return;
+        }
+
+        let u = self.order[depth];
+        for v in self.candidates(u) {
+            if !self.feasible(u, v) {
+                continue;
+            }
+            self.assign(u, v, true);
+            self.extend(depth + 1);
+            self.assign(u, v, false);
+            if self.found.len() >= self.limit {
+                return;
+            }
+        }
+    }
+
+    /// Target nodes worth trying for `u`: the matching
+    /// neighbours of the image of a mapped neighbour when
+    /// there is one, else every free target node.
+    fn candidates(&self, u: usize) -> Vec<usize> {
+        let from_pred = self.a.pred[u]
+            .iter()
+            .find_map(|&p| self.core1[p])
+            .map(|m| &self.b.succ[m]);
+        let from_succ = self.a.succ[u]
+            .iter()
+            .find_map(|&s| self.core1[s])
+            .map(|m| &self.b.pred[m]);
+        match from_pred.or(from_succ) {
+            Some(list) => list.iter().copied().filter(|&v| self.core2[v].is_none()).collect(),
+            None => (0..self.b.len()).filter(|&v| self.core2[v].is_none()).collect(),
+        }
+    }
+
+    fn feasible(&self, u: usize, v: usize) -> bool {
+        let exact = self.problem != Problem::Monomorphism;
+        let iso = self.problem == Problem::Isomorphism;
+        let (a, b) = (&self.a, &self.b);
+
+        let degrees = [
+            (a.succ[u].len(), b.succ[v].len()),
+            (a.pred[u].len(), b.pred[v].len()),
+        ];
+        if degrees.iter().any(|&(x, y)| if iso { x != y } else { x > y }) {
+            return false;
+        }
+        if let Some(f) = &self.vf2.node_match {
+            if !f(self.vf2.g1.node_data(u), self.vf2.g2.node_data(v)) {
+                return false;
+            }
+        }
+
+        let (loop1, loop2) = (a.has_edge(u, u), b.has_edge(v, v));
+        if (loop1 && !loop2) || (exact && loop2 && !loop1) {
+            return false;
+        }
+        if loop1 && !self.weights_match((u, u), (v, v)) {
+            return false;
+        }
+
+        // Every mapped pattern edge at `u` needs its image.
+        for &s in &a.succ[u] {
+            if let Some(m) = self.core1[s] {
+                if !b.has_edge(v, m) || !self.weights_match((u, s), (v, m)) {
+                    return false;
+                }
+            }
+        }
+        for &p in &a.pred[u] {
+            if let Some(m) = self.core1[p] {
+                if !b.has_edge(m, v) || !self.weights_match((p, u), (m, v)) {
+                    return false;
+                }
+            }
+        }
+        // And the other way round unless extra target edges
+        // are allowed.
+        if exact {
+            let missing = |list: &[usize], reverse: bool| {
+                list.iter().any(|&t| {
+                    self.core2[t].is_some_and(|s| {
+                        if reverse { !a.has_edge(s, u) } else { !a.has_edge(u, s) }
+                    })
+                })
+            };
+            if missing(&b.succ[v], false) || missing(&b.pred[v], true) {
+                return false;
+            }
+        }
+
+        // Look-ahead: free neighbours of `u`, in and outside
+        // the terminal set, must find room around `v`.
+        let count = |list: &[usize], core: &[Option<usize>], term: &[usize]| {
+            let free = list.iter().filter(|&&x| core[x].is_none());
+            let terminal = free.clone().filter(|&&x| term[x] > 0).count();
+            (terminal, free.count())
+        };
+        for (l1, l2) in [(&a.succ[u], &b.succ[v]), (&a.pred[u], &b.pred[v])] {
+            let (t1, f1) = count(l1, &self.core1, &self.term1);
+            let (t2, f2) = count(l2, &self.core2, &self.term2);
+            if t1 > t2 || f1 > f2 || (iso && (t1 != t2 || f1 != f2)) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn weights_match(&self, e1: (usize, usize), e2: (usize, usize)) -> bool {
+        match &self.vf2.edge_match {
+            Some(f) => f(self.a.weights[&e1], self.b.weights[&e2]),
+            None => true,
+        }
+    }
+
+    fn assign(&mut self, u: usize, v: usize, add: bool) {
+        self.core1[u] = if add { Some(v) } else { None };
+        self.core2[v] = if add { Some(u) } else { None };
+        for x in self.a.succ[u].iter().chain(&self.a.pred[u]) {
+            if add { self.term1[*x] += 1 } else { self.term1[*x] -= 1 }
+        }
+        for y in self.b.succ[v].iter().chain(&self.b.pred[v]) {
+            if add { self.term2[*y] += 1 } else { self.term2[*y] -= 1 }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Graph, Undirected};
+    use crate::mtx_graph;
+
+    use super::*;
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Graph<usize, Undirected, ()> {
+        let mut g = Graph::<_, Undirected, ()>::new();
+        for i in 0..n {
+            g.add_node(i);
+        }
+        for &(a, b) in edges {
+            g.add_edge(a, b, ());
+        }
+        g
+    }
+
+    #[test]
+    fn across_backends() {
+        let cycle = undirected(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+        let mut mg = mtx_graph::graph::Graph::<u8, mtx_graph::graph::Undirected, mtx_graph::graph::Unweighted>::default();
+        let ids: Vec<_> = (0..5).map(|i| mg.add_node(i)).collect();
+        for (a, b) in [(0, 2), (2, 4), (4, 1), (1, 3), (3, 0)] {
+            mg.add_edge(ids[a], ids[b]);
+        }
+        let vf2 = Vf2::new(&cycle, &mg);
+        let mapping = vf2.find(Problem::Isomorphism).unwrap();
+        for &(a, b) in &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)] {
+            assert!(mg.has_edge(mapping[a].1, mapping[b].1));
+        }
+        // A cycle has 2n automorphisms.
+        assert_eq!(vf2.find_all(Problem::Isomorphism).len(), 10);
+
+        let path = undirected(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+        let star = undirected(5, &[(0, 1), (0, 2), (0, 3), (0, 4)]);
+        assert!(!Vf2::new(&path, &star).is_isomorphic());
+    }
+
+    #[test]
+    fn subgraphs() {
+        let k4 = undirected(4, &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+        let triangle = undirected(3, &[(0, 1), (1, 2), (2, 0)]);
+        let path = undirected(3, &[(0, 1), (1, 2)]);
+        assert!(Vf2::new(&triangle, &k4).is_subgraph_isomorphic());
+        assert!(!Vf2::new(&path, &k4).is_subgraph_isomorphic());
+        assert!(Vf2::new(&path, &k4).is_monomorphic());
+        assert_eq!(Vf2::new(&path, &k4).find_all(Problem::Monomorphism).len(), 24);
+        assert!(!Vf2::new(&k4, &triangle).is_monomorphic());
+
+        // Directed edges must keep their direction.
+        let directed = |edges: &[(usize, usize)]| {
+            let mut g = Graph::<(), Directed, u32>::new();
+            for _ in 0..3 {
+                g.add_node(());
+            }
+            for &(a, b) in edges {
+                g.add_edge(a, b, 1);
+            }
+            g
+        };
+        let cycle = directed(&[(0, 1), (1, 2), (2, 0)]);
+        let chain = directed(&[(0, 1), (1, 2)]);
+        let fork = directed(&[(0, 1), (2, 1)]);
+        assert!(Vf2::new(&chain, &cycle).is_monomorphic());
+        assert!(!Vf2::new(&chain, &cycle).is_subgraph_isomorphic());
+        assert!(!Vf2::new(&fork, &cycle).is_monomorphic());
+    }
+
+    #[test]
+    fn matching_closures() {
+        let mut g1 = Graph::<char, Undirected, u32>::new();
+        let mut g2 = Graph::<char, Undirected, u32>::new();
+        for c in ['r', 'g', 'b'] {
+            g1.add_node(c);
+        }
+        for c in ['g', 'b', 'r'] {
+            g2.add_node(c);
+        }
+        g1.add_edge(0, 1, 5);
+        g1.add_edge(1, 2, 7);
+        g2.add_edge(2, 0, 5);
+        g2.add_edge(0, 1, 7);
+
+        let by_colour = Vf2::new(&g1, &g2).node_match(|a, b| a == b);
+        assert_eq!(by_colour.find(Problem::Isomorphism), Some(vec![(0, 2), (1, 0), (2, 1)]));
+        assert_eq!(by_colour.find_all(Problem::Isomorphism).len(), 1);
+
+        let heavy = Vf2::new(&g1, &g2).edge_match(|a, b| a == b);
+        assert_eq!(heavy.find_all(Problem::Isomorphism).len(), 1);
+        let off = Vf2::new(&g1, &g2).edge_match(|a, b| a + 1 == b);
+        assert!(!off.is_isomorphic());
+    }
+}
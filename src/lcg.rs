@@ -0,0 +1,28 @@
+/// Small seeded pseudo random number generator: a 64-bit linear
+/// congruential generator with Knuth's MMIX constants, keeping the
+/// high bits. The same seed always gives the same sequence, which
+/// is what seeded algorithms and randomised tests need.
+pub(crate) struct Lcg(u64);
+
+impl Lcg {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// A number in `0..m`.
+    pub(crate) fn below(&mut self, m: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) as usize % m
+    }
+
+    /// Fisher-Yates shuffle.
+    pub(crate) fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
@@ -1,3 +1,23 @@
+pub mod adjacency;
+pub(crate) mod queue;
+pub(crate) mod lcg;
 pub mod mtx_graph;
 pub mod list_graph;
 pub mod transitive_closure;
+pub mod flow;
+pub mod cut;
+pub mod matching;
+pub mod components;
+pub mod dominators;
+pub mod cycles;
+pub mod euler;
+pub mod coloring;
+pub mod cliques;
+pub mod isomorphism;
+pub mod planarity;
+pub mod centrality;
+pub mod community;
+pub mod cores;
+pub mod triangles;
+pub mod metrics;
+pub mod io;
@@ -1,5 +1,7 @@
 use std::{marker::PhantomData, ops::Index};
 
+use crate::adjacency::EdgeType;
+
 use super::{node::Node, edge::Edge, iter::BFS};
 
 #[derive(Debug, Clone, Copy)]
@@ -66,8 +68,18 @@ impl<V, D, E> Graph<V, D, E> {
         self.nodes.len()
     }
 
-    pub fn bfs(&self, start: usize) -> BFS<V, D, E> {
-        BFS::new(&self, start)
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn bfs(&self, start: usize) -> BFS<'_, V, D, E> {
+        BFS::new(self, start)
+    }
+}
+
+impl<V, D, E> Default for Graph<V, D, E> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -90,6 +102,21 @@ where
     }
 }
 
+impl<V, D, E> Graph<V, D, E>
+where
+    D: EdgeType,
+    E: Copy,
+{
+    /// Adds an edge the way `add_edge` does for `D`, for
+    /// callers that are generic over the edge type.
+    pub(crate) fn insert_edge(&mut self, a: usize, b: usize, weight: E) {
+        self.nodes[a].edges.push(Edge::new(weight, b));
+        if !D::DIRECTED {
+            self.nodes[b].edges.push(Edge::new(weight, a));
+        }
+    }
+}
+
 impl<V, D, E> Index<usize> for Graph<V, D, E> {
     type Output = Node<V, E>;
 
@@ -0,0 +1,119 @@
+use std::collections::VecDeque;
+
+use crate::adjacency::{undirected_adjacency, Adjacency};
+
+/// Checks whether a graph is bipartite by 2-colouring it with
+/// bfs. Directed edges are followed both ways.
+///
+/// On success returns the colour of every node, indexed by node
+/// position. Otherwise returns an odd cycle as a witness, as the
+/// sequence of its nodes (the last one links back to the first).
+/// Time complexity:
+///   adj-matrix graph: O(V^2)
+///   adj-list graph: O(V + E)
+pub fn bipartition<G: Adjacency>(graph: &G) -> Result<Vec<bool>, Vec<G::NodeId>> {
+    let adj = undirected_adjacency(graph);
+    colour(&adj).map_err(|cycle| cycle.into_iter().map(|p| graph.node_id(p)).collect())
+}
+
+/// Whether the graph can be 2-coloured.
+pub fn is_bipartite<G: Adjacency>(graph: &G) -> bool {
+    bipartition(graph).is_ok()
+}
+
+/// 2-colours the given neighbour lists, or finds an odd cycle.
+pub(crate) fn colour(adj: &[Vec<usize>]) -> Result<Vec<bool>, Vec<usize>> {
+    let n = adj.len();
+    let mut colour: Vec<Option<bool>> = vec![None; n];
+    let mut parent = vec![usize::MAX; n];
+    let mut depth = vec![0; n];
+    for root in 0..n {
+        if colour[root].is_some() {
+            continue;
+        }
+
+        colour[root] = Some(false);
+        let mut frontier = VecDeque::new();
+        frontier.push_back(root);
+        while let Some(u) = frontier.pop_front() {
+            for &v in adj[u].iter() {
+                match colour[v] {
+                    None => {
+                        colour[v] = colour[u].map(|c| !c);
+                        parent[v] = u;
+                        depth[v] = depth[u] + 1;
+                        frontier.push_back(v);
+                    }
+                    Some(c) if Some(c) == colour[u] => {
+                        return Err(odd_cycle(&parent, &depth, u, v));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(colour.into_iter().map(|c| c.unwrap_or(false)).collect())
+}
+
+/// Closes the cycle formed by the bfs tree paths of `u` and `v`
+/// and the same coloured edge between them.
+fn odd_cycle(parent: &[usize], depth: &[usize], mut u: usize, mut v: usize) -> Vec<usize> {
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    while depth[u] > depth[v] {
+        left.push(u);
+        u = parent[u];
+    }
+    while depth[v] > depth[u] {
+        right.push(v);
+        v = parent[v];
+    }
+    while u != v {
+        left.push(u);
+        right.push(v);
+        u = parent[u];
+        v = parent[v];
+    }
+    left.push(u);
+    left.extend(right.into_iter().rev());
+    left
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::Graph;
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn even_cycle() {
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..4).map(|_| g.add_node(())).collect();
+        for i in 0..4 {
+            g.add_edge(v[i], v[(i + 1) % 4], 1);
+        }
+        let colours = bipartition(&g).unwrap();
+        for i in 0..4 {
+            assert_ne!(colours[i], colours[(i + 1) % 4]);
+        }
+    }
+
+    #[test]
+    fn odd_cycle_witness() {
+        // A triangle hanging off a path.
+        let mut g = mtx::Graph::<u8>::default();
+        let v: Vec<_> = (0..5).map(|i| g.add_node(i)).collect();
+        g.add_edge(v[0], v[1]);
+        g.add_edge(v[1], v[2]);
+        g.add_edge(v[2], v[3]);
+        g.add_edge(v[3], v[4]);
+        g.add_edge(v[4], v[2]);
+        let cycle = bipartition(&g).unwrap_err();
+        assert_eq!(cycle.len(), 3);
+        for i in 0..cycle.len() {
+            assert!(g.has_edge(cycle[i], cycle[(i + 1) % cycle.len()]));
+        }
+    }
+}
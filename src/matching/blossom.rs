@@ -0,0 +1,825 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::adjacency::{undirected_adjacency, Adjacency, Measure};
+
+const NONE: usize = usize::MAX;
+
+/// Computes a maximum cardinality matching of a general graph
+/// using Edmonds' blossom algorithm: augmenting paths are grown
+/// by bfs and odd cycles (blossoms) are contracted on the fly.
+/// Directed edges are followed both ways.
+///
+/// Returns the matched pairs, each once.
+/// Time complexity:
+///   O(V^3)
+pub fn max_matching<G: Adjacency>(graph: &G) -> Vec<(G::NodeId, G::NodeId)> {
+    let adj = undirected_adjacency(graph);
+    let mut search = Blossom::new(&adj);
+    for root in 0..adj.len() {
+        if search.mate[root] != NONE {
+            continue;
+        }
+        if let Some(mut u) = search.find_path(root) {
+            // Flip the augmenting path ending at `u`.
+            while u != NONE {
+                let pu = search.parent[u];
+                let next = search.mate[pu];
+                search.mate[u] = pu;
+                search.mate[pu] = u;
+                u = next;
+            }
+        }
+    }
+
+    (0..adj.len())
+        .filter(|&u| search.mate[u] != NONE && u < search.mate[u])
+        .map(|u| (graph.node_id(u), graph.node_id(search.mate[u])))
+        .collect()
+}
+
+struct Blossom<'a> {
+    adj: &'a [Vec<usize>],
+    mate: Vec<usize>,
+    parent: Vec<usize>,
+    base: Vec<usize>,
+    used: Vec<bool>,
+    in_blossom: Vec<bool>,
+    queue: VecDeque<usize>,
+}
+
+impl<'a> Blossom<'a> {
+    fn new(adj: &'a [Vec<usize>]) -> Self {
+        let n = adj.len();
+        Self {
+            adj,
+            mate: vec![NONE; n],
+            parent: vec![NONE; n],
+            base: (0..n).collect(),
+            used: vec![false; n],
+            in_blossom: vec![false; n],
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Bfs over alternating paths from `root`, returning the
+    /// free node an augmenting path ends at.
+    fn find_path(&mut self, root: usize) -> Option<usize> {
+        let n = self.adj.len();
+        self.used = vec![false; n];
+        self.parent = vec![NONE; n];
+        self.base = (0..n).collect();
+        self.queue.clear();
+        self.used[root] = true;
+        self.queue.push_back(root);
+
+        while let Some(v) = self.queue.pop_front() {
+            for i in 0..self.adj[v].len() {
+                let to = self.adj[v][i];
+                if self.base[v] == self.base[to] || self.mate[v] == to {
+                    continue;
+                }
+
+                if to == root || (self.mate[to] != NONE && self.parent[self.mate[to]] != NONE) {
+                    self.contract(v, to);
+                } else if self.parent[to] == NONE {
+                    self.parent[to] = v;
+                    if self.mate[to] == NONE {
+                        return Some(to);
+                    }
+                    let next = self.mate[to];
+                    self.used[next] = true;
+                    self.queue.push_back(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Shrinks the blossom closed by the edge `v`-`to`.
+    fn contract(&mut self, v: usize, to: usize) {
+        let cur = self.lca(v, to);
+        self.in_blossom = vec![false; self.adj.len()];
+        self.mark_path(v, cur, to);
+        self.mark_path(to, cur, v);
+        for i in 0..self.adj.len() {
+            if self.in_blossom[self.base[i]] {
+                self.base[i] = cur;
+                if !self.used[i] {
+                    self.used[i] = true;
+                    self.queue.push_back(i);
+                }
+            }
+        }
+    }
+
+    fn lca(&self, mut a: usize, mut b: usize) -> usize {
+        let mut seen = vec![false; self.adj.len()];
+        loop {
+            a = self.base[a];
+            seen[a] = true;
+            if self.mate[a] == NONE {
+                break;
+            }
+            a = self.parent[self.mate[a]];
+        }
+        loop {
+            b = self.base[b];
+            if seen[b] {
+                return b;
+            }
+            b = self.parent[self.mate[b]];
+        }
+    }
+
+    fn mark_path(&mut self, mut v: usize, b: usize, mut child: usize) {
+        while self.base[v] != b {
+            self.in_blossom[self.base[v]] = true;
+            self.in_blossom[self.base[self.mate[v]]] = true;
+            self.parent[v] = child;
+            child = self.mate[v];
+            v = self.parent[self.mate[v]];
+        }
+    }
+}
+
+/// Computes a maximum weight matching of a general graph with
+/// the primal-dual blossom algorithm of Edmonds, in the O(V^3)
+/// formulation by Galil. Directed edges are followed both ways;
+/// between any pair of nodes only the heaviest edge counts.
+///
+/// With `max_cardinality` set, the heaviest among the matchings
+/// of maximum size is returned instead.
+/// Time complexity:
+///   O(V^3)
+pub fn max_weight_matching<G>(graph: &G, max_cardinality: bool) -> Vec<(G::NodeId, G::NodeId)>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let mut best: HashMap<(usize, usize), i64> = HashMap::new();
+    for u in 0..graph.node_count() {
+        for (v, w) in graph.out_edges(u) {
+            if u == v {
+                continue;
+            }
+            let entry = best.entry((u.min(v), u.max(v))).or_insert(i64::MIN);
+            *entry = (*entry).max(w.to_i64());
+        }
+    }
+    let mut edges: Vec<(usize, usize, i64)> = best.into_iter().map(|((u, v), w)| (u, v, 2 * w)).collect();
+    edges.sort_unstable();
+
+    let mate = WeightedBlossom::new(graph.node_count(), edges).solve(max_cardinality);
+    (0..mate.len())
+        .filter(|&u| mate[u] != NONE && u < mate[u])
+        .map(|u| (graph.node_id(u), graph.node_id(mate[u])))
+        .collect()
+}
+
+/// State of the weighted blossom algorithm. Edge `k` has the two
+/// endpoints `2k` and `2k + 1`; blossoms are numbered from `n`
+/// upwards, after the single vertices.
+struct WeightedBlossom {
+    n: usize,
+    edges: Vec<(usize, usize, i64)>,
+    endpoint: Vec<usize>,
+    neighbend: Vec<Vec<usize>>,
+    mate: Vec<usize>,
+    label: Vec<u8>,
+    labelend: Vec<usize>,
+    inblossom: Vec<usize>,
+    blossomparent: Vec<usize>,
+    blossomchilds: Vec<Vec<usize>>,
+    blossombase: Vec<usize>,
+    blossomendps: Vec<Vec<usize>>,
+    bestedge: Vec<usize>,
+    blossombestedges: Vec<Option<Vec<usize>>>,
+    unusedblossoms: Vec<usize>,
+    dualvar: Vec<i64>,
+    allowedge: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+impl WeightedBlossom {
+    fn new(n: usize, edges: Vec<(usize, usize, i64)>) -> Self {
+        let maxweight = edges.iter().map(|e| e.2).max().unwrap_or(0).max(0);
+        let endpoint = (0..2 * edges.len())
+            .map(|p| if p % 2 == 0 { edges[p / 2].0 } else { edges[p / 2].1 })
+            .collect();
+        let mut neighbend = vec![Vec::new(); n];
+        for (k, &(i, j, _)) in edges.iter().enumerate() {
+            neighbend[i].push(2 * k + 1);
+            neighbend[j].push(2 * k);
+        }
+        let mut dualvar = vec![maxweight; n];
+        dualvar.extend(vec![0; n]);
+        let mut blossombase: Vec<usize> = (0..n).collect();
+        blossombase.extend(vec![NONE; n]);
+
+        Self {
+            n,
+            endpoint,
+            neighbend,
+            mate: vec![NONE; n],
+            label: vec![0; 2 * n],
+            labelend: vec![NONE; 2 * n],
+            inblossom: (0..n).collect(),
+            blossomparent: vec![NONE; 2 * n],
+            blossomchilds: vec![Vec::new(); 2 * n],
+            blossombase,
+            blossomendps: vec![Vec::new(); 2 * n],
+            bestedge: vec![NONE; 2 * n],
+            blossombestedges: vec![None; 2 * n],
+            unusedblossoms: (n..2 * n).collect(),
+            dualvar,
+            allowedge: vec![false; edges.len()],
+            queue: Vec::new(),
+            edges,
+        }
+    }
+
+    fn slack(&self, k: usize) -> i64 {
+        let (i, j, w) = self.edges[k];
+        self.dualvar[i] + self.dualvar[j] - 2 * w
+    }
+
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        if b < self.n {
+            return vec![b];
+        }
+        let mut out = Vec::new();
+        let mut stack = vec![b];
+        while let Some(t) = stack.pop() {
+            if t < self.n {
+                out.push(t);
+            } else {
+                stack.extend(self.blossomchilds[t].iter().rev());
+            }
+        }
+        out
+    }
+
+    fn assign_label(&mut self, w: usize, t: u8, p: usize) {
+        let b = self.inblossom[w];
+        self.label[w] = t;
+        self.label[b] = t;
+        self.labelend[w] = p;
+        self.labelend[b] = p;
+        self.bestedge[w] = NONE;
+        self.bestedge[b] = NONE;
+        if t == 1 {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else if t == 2 {
+            let base = self.blossombase[b];
+            let m = self.mate[base];
+            self.assign_label(self.endpoint[m], 1, m ^ 1);
+        }
+    }
+
+    /// Traces back from `v` and `w` to find either a new
+    /// blossom's base or, if the paths never meet, `NONE`
+    /// (an augmenting path).
+    fn scan_blossom(&mut self, mut v: usize, mut w: usize) -> usize {
+        let mut path = Vec::new();
+        let mut base = NONE;
+        while v != NONE || w != NONE {
+            let mut b = self.inblossom[v];
+            if self.label[b] & 4 != 0 {
+                base = self.blossombase[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = 5;
+            if self.labelend[b] == NONE {
+                v = NONE;
+            } else {
+                v = self.endpoint[self.labelend[b]];
+                b = self.inblossom[v];
+                v = self.endpoint[self.labelend[b]];
+            }
+            if w != NONE {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = 1;
+        }
+        base
+    }
+
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (mut v, mut w, _) = self.edges[k];
+        let bb = self.inblossom[base];
+        let mut bv = self.inblossom[v];
+        let mut bw = self.inblossom[w];
+        let b = self.unusedblossoms.pop().unwrap();
+        self.blossombase[b] = base;
+        self.blossomparent[b] = NONE;
+        self.blossomparent[bb] = b;
+        let mut path = Vec::new();
+        let mut endps = Vec::new();
+        while bv != bb {
+            self.blossomparent[bv] = b;
+            path.push(bv);
+            endps.push(self.labelend[bv]);
+            v = self.endpoint[self.labelend[bv]];
+            bv = self.inblossom[v];
+        }
+        path.push(bb);
+        path.reverse();
+        endps.reverse();
+        endps.push(2 * k);
+        while bw != bb {
+            self.blossomparent[bw] = b;
+            path.push(bw);
+            endps.push(self.labelend[bw] ^ 1);
+            w = self.endpoint[self.labelend[bw]];
+            bw = self.inblossom[w];
+        }
+        self.label[b] = 1;
+        self.labelend[b] = self.labelend[bb];
+        self.dualvar[b] = 0;
+        self.blossomchilds[b] = path.clone();
+        self.blossomendps[b] = endps;
+        for v in self.leaves(b) {
+            if self.label[self.inblossom[v]] == 2 {
+                self.queue.push(v);
+            }
+            self.inblossom[v] = b;
+        }
+
+        // Remember the least-slack edge towards every
+        // neighbouring S-blossom.
+        let mut bestedgeto = vec![NONE; 2 * self.n];
+        for &bv in path.iter() {
+            let nblists: Vec<usize> = match self.blossombestedges[bv].take() {
+                Some(list) => list,
+                None => self
+                    .leaves(bv)
+                    .into_iter()
+                    .flat_map(|v| self.neighbend[v].iter().map(|p| p / 2))
+                    .collect(),
+            };
+            for k in nblists {
+                let (i, j, _) = self.edges[k];
+                let j = if self.inblossom[j] == b { i } else { j };
+                let bj = self.inblossom[j];
+                if bj != b
+                    && self.label[bj] == 1
+                    && (bestedgeto[bj] == NONE || self.slack(k) < self.slack(bestedgeto[bj]))
+                {
+                    bestedgeto[bj] = k;
+                }
+            }
+            self.bestedge[bv] = NONE;
+        }
+        let list: Vec<usize> = bestedgeto.into_iter().filter(|&k| k != NONE).collect();
+        self.bestedge[b] = NONE;
+        for &k in list.iter() {
+            if self.bestedge[b] == NONE || self.slack(k) < self.slack(self.bestedge[b]) {
+                self.bestedge[b] = k;
+            }
+        }
+        self.blossombestedges[b] = Some(list);
+    }
+
+    fn expand_blossom(&mut self, b: usize, endstage: bool) {
+        for s in self.blossomchilds[b].clone() {
+            self.blossomparent[s] = NONE;
+            if s < self.n {
+                self.inblossom[s] = s;
+            } else if endstage && self.dualvar[s] == 0 {
+                self.expand_blossom(s, endstage);
+            } else {
+                for v in self.leaves(s) {
+                    self.inblossom[v] = s;
+                }
+            }
+        }
+
+        if !endstage && self.label[b] == 2 {
+            // Relabel the children along the even side of the
+            // blossom, from the entry child to the base.
+            let childs = self.blossomchilds[b].clone();
+            let endps = self.blossomendps[b].clone();
+            let len = childs.len() as isize;
+            let at = |j: isize| ((j % len + len) % len) as usize;
+            let entrychild = self.inblossom[self.endpoint[self.labelend[b] ^ 1]];
+            let mut j = childs.iter().position(|&c| c == entrychild).unwrap() as isize;
+            let (jstep, endptrick) = if j & 1 == 1 {
+                j -= len;
+                (1, 0)
+            } else {
+                (-1, 1)
+            };
+            let mut p = self.labelend[b];
+            while j != 0 {
+                self.label[self.endpoint[p ^ 1]] = 0;
+                let q = endps[at(j - endptrick)];
+                self.label[self.endpoint[q ^ endptrick as usize ^ 1]] = 0;
+                self.assign_label(self.endpoint[p ^ 1], 2, p);
+                self.allowedge[q / 2] = true;
+                j += jstep;
+                p = endps[at(j - endptrick)] ^ endptrick as usize;
+                self.allowedge[p / 2] = true;
+                j += jstep;
+            }
+            let bv = childs[at(j)];
+            self.label[self.endpoint[p ^ 1]] = 2;
+            self.label[bv] = 2;
+            self.labelend[self.endpoint[p ^ 1]] = p;
+            self.labelend[bv] = p;
+            self.bestedge[bv] = NONE;
+            j += jstep;
+            while childs[at(j)] != entrychild {
+                let bv = childs[at(j)];
+                if self.label[bv] == 1 {
+                    j += jstep;
+                    continue;
+                }
+                if let Some(v) = self.leaves(bv).into_iter().find(|&v| self.label[v] != 0) {
+                    self.label[v] = 0;
+                    let m = self.mate[self.blossombase[bv]];
+                    self.label[self.endpoint[m]] = 0;
+                    self.assign_label(v, 2, self.labelend[v]);
+                }
+                j += jstep;
+            }
+        }
+
+        self.label[b] = 0;
+        self.labelend[b] = NONE;
+        self.blossomchilds[b].clear();
+        self.blossomendps[b].clear();
+        self.blossombase[b] = NONE;
+        self.blossombestedges[b] = None;
+        self.bestedge[b] = NONE;
+        self.unusedblossoms.push(b);
+    }
+
+    /// Swaps matched and unmatched edges inside blossom `b` so
+    /// that `v` becomes its base.
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.blossomparent[t] != b {
+            t = self.blossomparent[t];
+        }
+        if t >= self.n {
+            self.augment_blossom(t, v);
+        }
+        let len = self.blossomchilds[b].len() as isize;
+        let at = |j: isize| ((j % len + len) % len) as usize;
+        let i = self.blossomchilds[b].iter().position(|&c| c == t).unwrap();
+        let mut j = i as isize;
+        let (jstep, endptrick) = if i & 1 == 1 {
+            j -= len;
+            (1, 0)
+        } else {
+            (-1, 1)
+        };
+        while j != 0 {
+            j += jstep;
+            let t = self.blossomchilds[b][at(j)];
+            let p = self.blossomendps[b][at(j - endptrick)] ^ endptrick as usize;
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p]);
+            }
+            j += jstep;
+            let t = self.blossomchilds[b][at(j)];
+            if t >= self.n {
+                self.augment_blossom(t, self.endpoint[p ^ 1]);
+            }
+            self.mate[self.endpoint[p]] = p ^ 1;
+            self.mate[self.endpoint[p ^ 1]] = p;
+        }
+        self.blossomchilds[b].rotate_left(i);
+        self.blossomendps[b].rotate_left(i);
+        self.blossombase[b] = self.blossombase[self.blossomchilds[b][0]];
+    }
+
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.inblossom[s];
+                if bs >= self.n {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = p;
+                if self.labelend[bs] == NONE {
+                    break;
+                }
+                let t = self.endpoint[self.labelend[bs]];
+                let bt = self.inblossom[t];
+                s = self.endpoint[self.labelend[bt]];
+                let j = self.endpoint[self.labelend[bt] ^ 1];
+                if bt >= self.n {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = self.labelend[bt];
+                p = self.labelend[bt] ^ 1;
+            }
+        }
+    }
+
+    /// Runs one stage per augmentation until no augmenting path
+    /// is left, returning the mate of every vertex.
+    fn solve(mut self, max_cardinality: bool) -> Vec<usize> {
+        let n = self.n;
+        for _ in 0..n {
+            self.label = vec![0; 2 * n];
+            self.bestedge = vec![NONE; 2 * n];
+            for b in n..2 * n {
+                self.blossombestedges[b] = None;
+            }
+            self.allowedge = vec![false; self.edges.len()];
+            self.queue.clear();
+            for v in 0..n {
+                if self.mate[v] == NONE && self.label[self.inblossom[v]] == 0 {
+                    self.assign_label(v, 1, NONE);
+                }
+            }
+
+            let mut augmented = false;
+            loop {
+                while !augmented {
+                    let v = match self.queue.pop() {
+                        Some(v) => v,
+                        None => break,
+                    };
+                    for p in self.neighbend[v].clone() {
+                        let k = p / 2;
+                        let w = self.endpoint[p];
+                        if self.inblossom[v] == self.inblossom[w] {
+                            continue;
+                        }
+                        let mut kslack = 0;
+                        if !self.allowedge[k] {
+                            kslack = self.slack(k);
+                            if kslack <= 0 {
+                                self.allowedge[k] = true;
+                            }
+                        }
+                        if self.allowedge[k] {
+                            if self.label[self.inblossom[w]] == 0 {
+                                self.assign_label(w, 2, p ^ 1);
+                            } else if self.label[self.inblossom[w]] == 1 {
+                                let base = self.scan_blossom(v, w);
+                                if base != NONE {
+                                    self.add_blossom(base, k);
+                                } else {
+                                    self.augment_matching(k);
+                                    augmented = true;
+                                    break;
+                                }
+                            } else if self.label[w] == 0 {
+                                self.label[w] = 2;
+                                self.labelend[w] = p ^ 1;
+                            }
+                        } else if self.label[self.inblossom[w]] == 1 {
+                            let b = self.inblossom[v];
+                            if self.bestedge[b] == NONE || kslack < self.slack(self.bestedge[b]) {
+                                self.bestedge[b] = k;
+                            }
+                        } else if self.label[w] == 0
+                            && (self.bestedge[w] == NONE || kslack < self.slack(self.bestedge[w]))
+                        {
+                            self.bestedge[w] = k;
+                        }
+                    }
+                }
+                if augmented {
+                    break;
+                }
+
+                // No progress possible with the current duals;
+                // find the largest safe dual adjustment.
+                let mut deltatype = 0;
+                let mut delta = 0;
+                let mut deltaedge = NONE;
+                let mut deltablossom = NONE;
+                if !max_cardinality {
+                    deltatype = 1;
+                    delta = *self.dualvar[..n].iter().min().unwrap_or(&0);
+                }
+                for v in 0..n {
+                    if self.label[self.inblossom[v]] == 0 && self.bestedge[v] != NONE {
+                        let d = self.slack(self.bestedge[v]);
+                        if deltatype == 0 || d < delta {
+                            delta = d;
+                            deltatype = 2;
+                            deltaedge = self.bestedge[v];
+                        }
+                    }
+                }
+                for b in 0..2 * n {
+                    if self.blossomparent[b] == NONE && self.label[b] == 1 && self.bestedge[b] != NONE {
+                        let d = self.slack(self.bestedge[b]) / 2;
+                        if deltatype == 0 || d < delta {
+                            delta = d;
+                            deltatype = 3;
+                            deltaedge = self.bestedge[b];
+                        }
+                    }
+                }
+                for b in n..2 * n {
+                    if self.blossombase[b] != NONE
+                        && self.blossomparent[b] == NONE
+                        && self.label[b] == 2
+                        && (deltatype == 0 || self.dualvar[b] < delta)
+                    {
+                        delta = self.dualvar[b];
+                        deltatype = 4;
+                        deltablossom = b;
+                    }
+                }
+                if deltatype == 0 {
+                    deltatype = 1;
+                    delta = (*self.dualvar[..n].iter().min().unwrap_or(&0)).max(0);
+                }
+
+                for v in 0..n {
+                    match self.label[self.inblossom[v]] {
+                        1 => self.dualvar[v] -= delta,
+                        2 => self.dualvar[v] += delta,
+                        _ => {}
+                    }
+                }
+                for b in n..2 * n {
+                    if self.blossombase[b] != NONE && self.blossomparent[b] == NONE {
+                        match self.label[b] {
+                            1 => self.dualvar[b] += delta,
+                            2 => self.dualvar[b] -= delta,
+                            _ => {}
+                        }
+                    }
+                }
+
+                match deltatype {
+                    1 => break,
+                    2 => {
+                        self.allowedge[deltaedge] = true;
+                        let (mut i, j, _) = self.edges[deltaedge];
+                        if self.label[self.inblossom[i]] == 0 {
+                            i = j;
+                        }
+                        self.queue.push(i);
+                    }
+                    3 => {
+                        self.allowedge[deltaedge] = true;
+                        let (i, _, _) = self.edges[deltaedge];
+                        self.queue.push(i);
+                    }
+                    _ => self.expand_blossom(deltablossom, false),
+                }
+            }
+
+            if !augmented {
+                break;
+            }
+
+            for b in n..2 * n {
+                if self.blossomparent[b] == NONE
+                    && self.blossombase[b] != NONE
+                    && self.label[b] == 1
+                    && self.dualvar[b] == 0
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+
+        self.mate
+            .iter()
+            .map(|&p| if p == NONE { NONE } else { self.endpoint[p] })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lcg::Lcg;
+    use crate::list_graph::graph::{Graph, Undirected};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    fn weight(g: &Graph<(), Undirected, i64>, pairs: &[(usize, usize)]) -> i64 {
+        pairs
+            .iter()
+            .map(|&(u, v)| g.edges(u).iter().filter(|e| e.next == v).map(|e| e.weight).max().unwrap())
+            .sum()
+    }
+
+    #[test]
+    fn odd_cycle_needs_blossom() {
+        // A 5-cycle with a pendant: greedy bfs from the
+        // wrong side gets stuck without contracting.
+        let mut g = mtx::Graph::<u8>::default();
+        let v: Vec<_> = (0..6).map(|i| g.add_node(i)).collect();
+        for i in 0..5 {
+            g.add_edge(v[i], v[(i + 1) % 5]);
+        }
+        g.add_edge(v[0], v[5]);
+        let m = max_matching(&g);
+        assert_eq!(m.len(), 3);
+    }
+
+    #[test]
+    fn petersen_is_perfect() {
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..10).map(|_| g.add_node(())).collect();
+        for i in 0..5 {
+            g.add_edge(v[i], v[(i + 1) % 5], 1);
+            g.add_edge(v[i], v[i + 5], 1);
+            g.add_edge(v[i + 5], v[(i + 2) % 5 + 5], 1);
+        }
+        assert_eq!(max_matching(&g).len(), 5);
+    }
+
+    #[test]
+    fn weighted_prefers_heavy_edge() {
+        // Path a - b - c - d where the middle edge
+        // outweighs both outer ones combined.
+        let mut g = Graph::<(), Undirected, i64>::new();
+        let v: Vec<usize> = (0..4).map(|_| g.add_node(())).collect();
+        g.add_edge(v[0], v[1], 2);
+        g.add_edge(v[1], v[2], 5);
+        g.add_edge(v[2], v[3], 2);
+        let m = max_weight_matching(&g, false);
+        assert_eq!(m, vec![(v[1], v[2])]);
+
+        let m = max_weight_matching(&g, true);
+        assert_eq!(m.len(), 2);
+        assert_eq!(weight(&g, &m), 4);
+    }
+
+    #[test]
+    fn weighted_blossom() {
+        // From the reference implementation's test suite: an
+        // S-blossom that must be formed before augmenting.
+        let mut g = Graph::<(), Undirected, i64>::new();
+        let v: Vec<usize> = (0..7).map(|_| g.add_node(())).collect();
+        for &(a, b, w) in &[(1, 2, 8), (1, 3, 9), (2, 3, 10), (3, 4, 7), (1, 6, 5), (4, 5, 6)] {
+            g.add_edge(v[a], v[b], w);
+        }
+        let mut m = max_weight_matching(&g, false);
+        m.sort_unstable();
+        assert_eq!(m, vec![(v[1], v[6]), (v[2], v[3]), (v[4], v[5])]);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        // Exhaustive best matching over subsets of nodes.
+        fn brute(n: usize, w: &[Vec<i64>], count: bool) -> (usize, i64) {
+            let mut best = vec![(0usize, 0i64); 1 << n];
+            for mask in 1..(1usize << n) {
+                let u = mask.trailing_zeros() as usize;
+                let rest = mask & !(1 << u);
+                let mut b = best[rest];
+                for v in (u + 1)..n {
+                    if rest & (1 << v) != 0 && w[u][v] != 0 {
+                        let (c, x) = best[rest & !(1 << v)];
+                        let cand = (c + 1, x + w[u][v]);
+                        let better = if count { cand > b } else { cand.1 > b.1 };
+                        if better {
+                            b = cand;
+                        }
+                    }
+                }
+                best[mask] = b;
+            }
+            best[(1 << n) - 1]
+        }
+
+        let mut rng = Lcg::new(7);
+        for _ in 0..200 {
+            let n = 2 + rng.below(8);
+            let mut g = Graph::<(), Undirected, i64>::new();
+            let mut w = vec![vec![0i64; n]; n];
+            for _ in 0..n {
+                g.add_node(());
+            }
+            let pairs: Vec<(usize, usize)> = (0..n)
+                .flat_map(|u| ((u + 1)..n).map(move |v| (u, v)))
+                .collect();
+            for (u, v) in pairs {
+                if rng.below(3) == 0 {
+                    let x = 1 + rng.below(20) as i64;
+                    w[u][v] = x;
+                    w[v][u] = x;
+                    g.add_edge(u, v, x);
+                }
+            }
+
+            let m = max_matching(&g);
+            assert_eq!(m.len(), brute(n, &w, true).0);
+            let m = max_weight_matching(&g, false);
+            assert_eq!(weight(&g, &m), brute(n, &w, false).1);
+            let m = max_weight_matching(&g, true);
+            assert_eq!((m.len(), weight(&g, &m)), brute(n, &w, true));
+        }
+    }
+}
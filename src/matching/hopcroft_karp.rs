@@ -0,0 +1,176 @@
+use std::collections::VecDeque;
+
+use crate::adjacency::{undirected_adjacency, Adjacency};
+
+use super::bipartite::colour;
+
+/// A maximum matching of a bipartite graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BipartiteMatching<I> {
+    /// Matched pairs, each as `(left, right)`.
+    pub pairs: Vec<(I, I)>,
+    /// A minimum vertex cover, the same size as the matching
+    /// by König's theorem.
+    pub vertex_cover: Vec<I>,
+}
+
+/// Computes a maximum cardinality matching of a bipartite graph
+/// with the Hopcroft-Karp algorithm, along with a minimum vertex
+/// cover derived from it via König's theorem. The two sides are
+/// found by 2-colouring the graph.
+///
+/// Returns `None` when the graph is not bipartite.
+/// Time complexity:
+///   O(E * sqrt(V))
+pub fn hopcroft_karp<G: Adjacency>(graph: &G) -> Option<BipartiteMatching<G::NodeId>> {
+    let adj = undirected_adjacency(graph);
+    let left: Vec<bool> = colour(&adj).ok()?.into_iter().map(|c| !c).collect();
+    let n = adj.len();
+
+    let mut mate: Vec<Option<usize>> = vec![None; n];
+    let mut dist = vec![usize::MAX; n];
+    while layer(&adj, &left, &mate, &mut dist) {
+        for u in 0..n {
+            if left[u] && mate[u].is_none() {
+                augment(&adj, &mut mate, &mut dist, u);
+            }
+        }
+    }
+
+    // König: walk alternating paths from the free left nodes.
+    let mut seen = vec![false; n];
+    let mut frontier: VecDeque<usize> = (0..n)
+        .filter(|&u| left[u] && mate[u].is_none())
+        .collect();
+    for &u in frontier.iter() {
+        seen[u] = true;
+    }
+    while let Some(u) = frontier.pop_front() {
+        for &v in adj[u].iter() {
+            if seen[v] || mate[u] == Some(v) {
+                continue;
+            }
+            seen[v] = true;
+            if let Some(w) = mate[v] {
+                if !seen[w] {
+                    seen[w] = true;
+                    frontier.push_back(w);
+                }
+            }
+        }
+    }
+
+    let pairs = (0..n)
+        .filter(|&u| left[u])
+        .filter_map(|u| mate[u].map(|v| (graph.node_id(u), graph.node_id(v))))
+        .collect();
+    let vertex_cover = (0..n)
+        .filter(|&u| if left[u] { !seen[u] } else { seen[u] })
+        .map(|u| graph.node_id(u))
+        .collect();
+
+    Some(BipartiteMatching {
+        pairs,
+        vertex_cover,
+    })
+}
+
+/// Bfs from every free left node over alternating paths,
+/// layering the left side. Returns whether a free right node
+/// was reached, i.e. an augmenting path exists.
+fn layer(adj: &[Vec<usize>], left: &[bool], mate: &[Option<usize>], dist: &mut [usize]) -> bool {
+    let mut frontier = VecDeque::new();
+    for u in 0..adj.len() {
+        if left[u] && mate[u].is_none() {
+            dist[u] = 0;
+            frontier.push_back(u);
+        } else {
+            dist[u] = usize::MAX;
+        }
+    }
+
+    let mut found = false;
+    while let Some(u) = frontier.pop_front() {
+        for &v in adj[u].iter() {
+            match mate[v] {
+                None => found = true,
+                Some(w) if dist[w] == usize::MAX => {
+                    dist[w] = dist[u] + 1;
+                    frontier.push_back(w);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    found
+}
+
+fn augment(adj: &[Vec<usize>], mate: &mut [Option<usize>], dist: &mut [usize], u: usize) -> bool {
+    for &v in adj[u].iter() {
+        let next = match mate[v] {
+            None => true,
+            Some(w) => dist[w] == dist[u] + 1 && augment(adj, mate, dist, w),
+        };
+        if next {
+            mate[u] = Some(v);
+            mate[v] = Some(u);
+            return true;
+        }
+    }
+
+    dist[u] = usize::MAX;
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::Graph;
+
+    use super::*;
+
+    #[test]
+    fn perfect_matching() {
+        // workers 0..3, jobs 3..6
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..6).map(|_| g.add_node(())).collect();
+        g.add_edge(v[0], v[3], 1);
+        g.add_edge(v[0], v[4], 1);
+        g.add_edge(v[1], v[3], 1);
+        g.add_edge(v[2], v[4], 1);
+        g.add_edge(v[2], v[5], 1);
+        let m = hopcroft_karp(&g).unwrap();
+        assert_eq!(m.pairs.len(), 3);
+        assert_eq!(m.vertex_cover.len(), 3);
+    }
+
+    #[test]
+    fn konig_cover() {
+        // A star plus a pendant edge: the centre and one
+        // leaf endpoint cover every edge.
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..6).map(|_| g.add_node(())).collect();
+        g.add_edge(v[0], v[1], 1);
+        g.add_edge(v[0], v[2], 1);
+        g.add_edge(v[0], v[3], 1);
+        g.add_edge(v[4], v[5], 1);
+        let m = hopcroft_karp(&g).unwrap();
+        assert_eq!(m.pairs.len(), 2);
+        assert_eq!(m.vertex_cover.len(), 2);
+        for u in 0..6 {
+            for e in g.edges(u) {
+                assert!(m.vertex_cover.contains(&u) || m.vertex_cover.contains(&e.next));
+            }
+        }
+    }
+
+    #[test]
+    fn not_bipartite() {
+        let mut g = Graph::<()>::new();
+        let v: Vec<usize> = (0..3).map(|_| g.add_node(())).collect();
+        g.add_edge(v[0], v[1], 1);
+        g.add_edge(v[1], v[2], 1);
+        g.add_edge(v[2], v[0], 1);
+        assert!(hopcroft_karp(&g).is_none());
+    }
+}
@@ -0,0 +1,236 @@
+use crate::adjacency::{Adjacency, Measure};
+
+/// An optimal assignment between two node sets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment<I> {
+    /// Assigned pairs, each as `(left, right)`.
+    pub pairs: Vec<(I, I)>,
+    /// Summed weight of the assigned edges.
+    pub total: i64,
+}
+
+/// Finds a minimum weight matching between `left` and `right`
+/// that covers every node of the smaller side, using the
+/// Hungarian (Kuhn-Munkres) algorithm. Only edges leaving a
+/// `left` node towards a `right` node are considered, so a
+/// weighted adjacency matrix acts directly as the cost matrix.
+/// Missing edges are forbidden assignments. The matrix backend
+/// stores no edge as weight 0, so there a zero cost is forbidden
+/// too; shift all costs up by one, or use the list backend, to
+/// allow free assignments.
+///
+/// Returns `None` when no such matching exists.
+/// Time complexity:
+///   O(n^2 * m) where n <= m are the two side lengths.
+pub fn min_weight_assignment<G>(
+    graph: &G,
+    left: &[G::NodeId],
+    right: &[G::NodeId],
+) -> Option<Assignment<G::NodeId>>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    assign(graph, left, right, 1)
+}
+
+/// Like `min_weight_assignment`, but maximises the summed
+/// weight instead.
+pub fn max_weight_assignment<G>(
+    graph: &G,
+    left: &[G::NodeId],
+    right: &[G::NodeId],
+) -> Option<Assignment<G::NodeId>>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    assign(graph, left, right, -1)
+}
+
+fn assign<G>(
+    graph: &G,
+    left: &[G::NodeId],
+    right: &[G::NodeId],
+    sign: i64,
+) -> Option<Assignment<G::NodeId>>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let mut col = vec![None; graph.node_count()];
+    for (j, &r) in right.iter().enumerate() {
+        col[graph.node_pos(r)] = Some(j);
+    }
+    let mut costs = vec![vec![None; right.len()]; left.len()];
+    for (i, &l) in left.iter().enumerate() {
+        for (to, w) in graph.out_edges(graph.node_pos(l)) {
+            if let Some(j) = col[to] {
+                let w = sign * w.to_i64();
+                costs[i][j] = Some(costs[i][j].map_or(w, |c: i64| c.min(w)));
+            }
+        }
+    }
+
+    let pairs: Vec<(usize, usize)> = if left.len() <= right.len() {
+        hungarian(&costs)?
+    } else {
+        let transposed: Vec<Vec<Option<i64>>> = (0..right.len())
+            .map(|j| costs.iter().map(|row| row[j]).collect())
+            .collect();
+        hungarian(&transposed)?
+            .into_iter()
+            .map(|(j, i)| (i, j))
+            .collect()
+    };
+
+    let total = pairs
+        .iter()
+        .filter_map(|&(i, j)| costs[i][j])
+        .sum::<i64>()
+        * sign;
+    Some(Assignment {
+        pairs: pairs.into_iter().map(|(i, j)| (left[i], right[j])).collect(),
+        total,
+    })
+}
+
+/// Solves the rectangular assignment problem for a cost matrix
+/// with at most as many rows as columns. `None` entries may not
+/// be assigned. Returns the chosen `(row, column)` of every row.
+pub(crate) fn hungarian(costs: &[Vec<Option<i64>>]) -> Option<Vec<(usize, usize)>> {
+    const INF: i64 = i64::MAX;
+    let n = costs.len();
+    let m = costs.first().map_or(0, |r| r.len());
+    // Potentials and the row matched to each column, all
+    // 1-indexed so that column 0 can act as a sentinel.
+    let mut u = vec![0i64; n + 1];
+    let mut v = vec![0i64; m + 1];
+    let mut p = vec![0usize; m + 1];
+    let mut way = vec![0usize; m + 1];
+
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut minv = vec![INF; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = INF;
+            let mut j1 = 0;
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                if let Some(c) = costs[i0 - 1][j - 1] {
+                    let cur = c - u[i0] - v[j];
+                    if cur < minv[j] {
+                        minv[j] = cur;
+                        way[j] = j0;
+                    }
+                }
+                if minv[j] < delta {
+                    delta = minv[j];
+                    j1 = j;
+                }
+            }
+            if delta == INF {
+                return None;
+            }
+
+            for j in 0..=m {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else if minv[j] != INF {
+                    minv[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+
+        // Flip the alternating path back to the sentinel.
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut pairs: Vec<(usize, usize)> = (1..=m)
+        .filter(|&j| p[j] != 0)
+        .map(|j| (p[j] - 1, j - 1))
+        .collect();
+    pairs.sort_unstable();
+    Some(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mtx_graph::graph::{Directed, Graph, Undirected, Weighted};
+
+    use super::*;
+
+    #[test]
+    fn square_min_and_max() {
+        // Cost matrix:
+        //      x y z
+        //   a  4 1 3
+        //   b  2 5 2
+        //   c  3 2 2
+        let mut g = Graph::<char, Directed, Weighted>::default();
+        let left: Vec<_> = "abc".chars().map(|c| g.add_node(c)).collect();
+        let right: Vec<_> = "xyz".chars().map(|c| g.add_node(c)).collect();
+        let costs = [[4, 1, 3], [2, 5, 2], [3, 2, 2]];
+        for (i, row) in costs.iter().enumerate() {
+            for (j, &c) in row.iter().enumerate() {
+                g.add_edge(left[i], right[j], c);
+            }
+        }
+
+        let min = min_weight_assignment(&g, &left, &right).unwrap();
+        assert_eq!(min.total, 5);
+        assert_eq!(min.pairs, vec![(left[0], right[1]), (left[1], right[0]), (left[2], right[2])]);
+
+        let max = max_weight_assignment(&g, &left, &right).unwrap();
+        assert_eq!(max.total, 11);
+    }
+
+    #[test]
+    fn rectangular() {
+        // Three workers, two jobs; the most expensive
+        // worker stays idle.
+        let mut g = Graph::<u8, Undirected, Weighted>::default();
+        let w: Vec<_> = (0..3).map(|i| g.add_node(i)).collect();
+        let j: Vec<_> = (3..5).map(|i| g.add_node(i)).collect();
+        g.add_edge(w[0], j[0], 9);
+        g.add_edge(w[0], j[1], 9);
+        g.add_edge(w[1], j[0], 1);
+        g.add_edge(w[1], j[1], 3);
+        g.add_edge(w[2], j[0], 2);
+        g.add_edge(w[2], j[1], 4);
+        let a = min_weight_assignment(&g, &w, &j).unwrap();
+        assert_eq!(a.total, 5);
+        assert_eq!(a.pairs.len(), 2);
+        assert!(a.pairs.iter().all(|&(l, _)| l != w[0]));
+    }
+
+    #[test]
+    fn infeasible() {
+        let mut g = Graph::<u8, Directed, Weighted>::default();
+        let a = g.add_node(0);
+        let b = g.add_node(1);
+        let x = g.add_node(2);
+        let y = g.add_node(3);
+        g.add_edge(a, x, 1);
+        g.add_edge(b, x, 1);
+        assert!(min_weight_assignment(&g, &[a, b], &[x, y]).is_none());
+    }
+}
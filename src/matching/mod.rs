@@ -0,0 +1,5 @@
+//! Bipartiteness and matchings.
+pub mod bipartite;
+pub mod hopcroft_karp;
+pub mod hungarian;
+pub mod blossom;
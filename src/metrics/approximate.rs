@@ -0,0 +1,162 @@
+use crate::list_graph::graph::{Graph, Undirected};
+
+use super::eccentricity::bfs_distances;
+
+/// Bounds on the diameter of a graph; exact when they meet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiameterBounds {
+    pub lower: usize,
+    pub upper: usize,
+}
+
+impl DiameterBounds {
+    pub fn is_exact(&self) -> bool {
+        self.lower == self.upper
+    }
+}
+
+/// Lower bound on the diameter from two breadth-first searches:
+/// one from `start` to a farthest node `a`, then one from `a`,
+/// whose eccentricity is the bound. Exact on trees. Only the
+/// component of `start` is considered. Returns `None` if `start`
+/// is not a node, e.g. in an empty graph.
+/// Time complexity:
+///   O(n + m)
+pub fn double_sweep<V, E>(graph: &Graph<V, Undirected, E>, start: usize) -> Option<usize> {
+    if start >= graph.len() {
+        return None;
+    }
+    let (a, _) = farthest(&bfs_distances(graph, start));
+    Some(farthest(&bfs_distances(graph, a)).1)
+}
+
+/// Diameter of a connected undirected graph by iFUB (Crescenzi et
+/// al.): a double sweep picks a central node `u`, then nodes are
+/// visited by decreasing distance from `u`, each level tightening
+/// `lower` through eccentricities and `upper` to twice the level,
+/// until the bounds meet. On most real graphs this takes a
+/// handful of searches. With `max_searches` (at least four) the
+/// run stops early and reports whatever bounds it has reached.
+/// Returns `None` for an empty or disconnected graph, or if
+/// `start` is not a node.
+/// Time complexity:
+///   O(n * (n + m)) in the worst case
+pub fn ifub_diameter<V, E>(
+    graph: &Graph<V, Undirected, E>,
+    start: usize,
+    max_searches: Option<usize>,
+) -> Option<DiameterBounds> {
+    if start >= graph.len() {
+        return None;
+    }
+    let budget = max_searches.unwrap_or(usize::MAX).max(4);
+    let from_start = bfs_distances(graph, start);
+    if from_start.iter().any(Option::is_none) {
+        return None;
+    }
+
+    // Double sweep, remembering the path to its far end.
+    let (a, _) = farthest(&from_start);
+    let from_a = bfs_distances(graph, a);
+    let (b, mut lower) = farthest(&from_a);
+    let from_b = bfs_distances(graph, b);
+    let u = (0..graph.len())
+        .find(|&v| from_a[v] == Some(lower / 2) && from_b[v] == Some(lower - lower / 2))
+        .unwrap_or(a);
+
+    let from_u = bfs_distances(graph, u);
+    let (_, ecc_u) = farthest(&from_u);
+    lower = lower.max(ecc_u);
+    let mut upper = 2 * ecc_u;
+    let mut searches = 4;
+
+    let mut levels = vec![Vec::new(); ecc_u + 1];
+    for (v, d) in from_u.iter().enumerate() {
+        levels[d.unwrap()].push(v);
+    }
+    for i in (1..=ecc_u).rev() {
+        // Nodes closer to `u` than level i are within 2(i-1)
+        // of each other, so once `lower` beats that, it is exact.
+        for &v in &levels[i] {
+            if lower >= upper {
+                break;
+            }
+            if searches == budget {
+                return Some(DiameterBounds { lower, upper });
+            }
+            lower = lower.max(farthest(&bfs_distances(graph, v)).1);
+            searches += 1;
+        }
+        upper = upper.min(lower.max(2 * (i - 1)));
+        if lower >= upper {
+            break;
+        }
+    }
+
+    Some(DiameterBounds { lower, upper: upper.max(lower) })
+}
+
+/// The farthest reached node and its distance.
+fn farthest(dist: &[Option<usize>]) -> (usize, usize) {
+    dist.iter()
+        .enumerate()
+        .filter_map(|(v, d)| d.map(|d| (v, d)))
+        .max_by_key(|&(v, d)| (d, std::cmp::Reverse(v)))
+        .unwrap_or((0, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lcg::Lcg;
+    use crate::metrics::eccentricity::eccentricity;
+
+    use super::*;
+
+    #[test]
+    fn matches_exact_diameter() {
+        let mut rng = Lcg::new(23);
+        for _ in 0..50 {
+            let n = 2 + rng.below(30);
+            let mut g = Graph::<_, Undirected, ()>::new();
+            for v in 0..n {
+                g.add_node(());
+                if v > 0 {
+                    // A random tree keeps the graph connected.
+                    let parent = rng.below(v);
+                    g.add_edge(parent, v, ());
+                }
+            }
+            for _ in 0..rng.below(n) {
+                let (a, b) = (rng.below(n), rng.below(n));
+                g.add_edge(a, b, ());
+            }
+
+            let exact = eccentricity(&g).unwrap().diameter().unwrap();
+            let bounds = ifub_diameter(&g, 0, None).unwrap();
+            assert_eq!(bounds, DiameterBounds { lower: exact, upper: exact });
+            assert!(double_sweep(&g, 0).unwrap() <= exact);
+            let partial = ifub_diameter(&g, 0, Some(4)).unwrap();
+            assert!(partial.lower <= exact && exact <= partial.upper);
+        }
+    }
+
+    #[test]
+    fn tree_double_sweep() {
+        let mut g = Graph::<_, Undirected, ()>::new();
+        for _ in 0..7 {
+            g.add_node(());
+        }
+        for (a, b) in [(0, 1), (1, 2), (2, 3), (1, 4), (4, 5), (5, 6)] {
+            g.add_edge(a, b, ());
+        }
+        assert_eq!(double_sweep(&g, 3), Some(5));
+        assert!(ifub_diameter(&g, 0, None).unwrap().is_exact());
+
+        g.add_node(());
+        assert_eq!(ifub_diameter(&g, 0, None), None);
+
+        let empty = Graph::<(), Undirected, ()>::new();
+        assert_eq!(double_sweep(&empty, 0), None);
+        assert_eq!(ifub_diameter(&empty, 0, None), None);
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::BinaryHeap;
+
+use crate::adjacency::{Adjacency, Measure};
+use crate::list_graph::graph::Graph;
+use crate::queue::QueueNode;
+
+/// The eccentricity of every node, the greatest distance from it
+/// to any other node, and the metrics derived from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eccentricity<I, W> {
+    pub(crate) ids: Vec<I>,
+    pub(crate) values: Vec<W>,
+}
+
+impl<I: Copy, W: Copy + Ord> Eccentricity<I, W> {
+    /// Eccentricities by node position.
+    pub fn values(&self) -> &[W] {
+        &self.values
+    }
+
+    /// The largest eccentricity; `None` for an empty graph.
+    pub fn diameter(&self) -> Option<W> {
+        self.values.iter().copied().max()
+    }
+
+    /// The smallest eccentricity; `None` for an empty graph.
+    pub fn radius(&self) -> Option<W> {
+        self.values.iter().copied().min()
+    }
+
+    /// Nodes whose eccentricity equals the radius.
+    pub fn center(&self) -> Vec<I> {
+        self.with_value(self.radius())
+    }
+
+    /// Nodes whose eccentricity equals the diameter.
+    pub fn periphery(&self) -> Vec<I> {
+        self.with_value(self.diameter())
+    }
+
+    fn with_value(&self, target: Option<W>) -> Vec<I> {
+        self.values
+            .iter()
+            .zip(&self.ids)
+            .filter(|(&v, _)| Some(v) == target)
+            .map(|(_, &id)| id)
+            .collect()
+    }
+}
+
+/// Hop count eccentricities, with one breadth-first search per
+/// node. Distances follow edge direction. Returns `None` unless
+/// every node reaches every other one.
+/// Time complexity:
+///   O(n * (n + m))
+pub fn eccentricity<V, D, E>(graph: &Graph<V, D, E>) -> Option<Eccentricity<usize, usize>> {
+    let values = (0..graph.len())
+        .map(|s| {
+            let dist = bfs_distances(graph, s);
+            dist.iter().try_fold(0, |m, d| d.map(|d| m.max(d)))
+        })
+        .collect::<Option<Vec<usize>>>()?;
+
+    Some(Eccentricity {
+        ids: (0..graph.len()).collect(),
+        values,
+    })
+}
+
+/// Eccentricities under edge weights, with one Dijkstra search per
+/// node. Weights must be non-negative. Returns `None` unless every
+/// node reaches every other one.
+/// Time complexity:
+///   O(n * m log n)
+pub fn weighted_eccentricity<G>(graph: &G) -> Option<Eccentricity<G::NodeId, i64>>
+where
+    G: Adjacency,
+    G::Weight: Measure,
+{
+    let n = graph.node_count();
+    let adj: Vec<Vec<(usize, i64)>> = (0..n)
+        .map(|p| graph.out_edges(p).into_iter().map(|(v, w)| (v, w.to_i64())).collect())
+        .collect();
+    let values = (0..n)
+        .map(|s| {
+            let dist = dijkstra(&adj, s);
+            dist.iter().try_fold(0, |m, d| d.map(|d| m.max(d)))
+        })
+        .collect::<Option<Vec<i64>>>()?;
+
+    Some(Eccentricity {
+        ids: (0..n).map(|p| graph.node_id(p)).collect(),
+        values,
+    })
+}
+
+/// Hop distances from `s`, read off the order in which the
+/// graph's breadth-first iterator yields nodes: every node is
+/// yielded after the one that discovered it.
+pub(crate) fn bfs_distances<V, D, E>(graph: &Graph<V, D, E>, s: usize) -> Vec<Option<usize>> {
+    let mut dist = vec![None; graph.len()];
+    dist[s] = Some(0);
+    for u in graph.bfs(s) {
+        let d = dist[u].unwrap() + 1;
+        for e in graph.edges(u) {
+            if dist[e.next].is_none() {
+                dist[e.next] = Some(d);
+            }
+        }
+    }
+    dist
+}
+
+fn dijkstra(adj: &[Vec<(usize, i64)>], s: usize) -> Vec<Option<i64>> {
+    let mut dist: Vec<Option<i64>> = vec![None; adj.len()];
+    let mut frontier = BinaryHeap::<QueueNode<usize, i64>>::new();
+    dist[s] = Some(0);
+    frontier.push(QueueNode::new(s, 0));
+    while let Some(current) = frontier.pop() {
+        if dist[current.idx].is_some_and(|d| d < current.weight) {
+            continue;
+        }
+        for &(next, w) in adj[current.idx].iter() {
+            let new_cost = current.weight + w;
+            if dist[next].is_none_or(|d| new_cost < d) {
+                dist[next] = Some(new_cost);
+                frontier.push(QueueNode::new(next, new_cost));
+            }
+        }
+    }
+    dist
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Directed, Undirected};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn path_metrics() {
+        let mut g = Graph::<_, Undirected, u32>::new();
+        for i in 0..5 {
+            g.add_node(());
+            if i > 0 {
+                g.add_edge(i - 1, i, 1);
+            }
+        }
+        let ecc = eccentricity(&g).unwrap();
+        assert_eq!(ecc.values(), &[4, 3, 2, 3, 4]);
+        assert_eq!(ecc.diameter(), Some(4));
+        assert_eq!(ecc.radius(), Some(2));
+        assert_eq!(ecc.center(), vec![2]);
+        assert_eq!(ecc.periphery(), vec![0, 4]);
+
+        let mut one_way = Graph::<_, Directed, u32>::new();
+        one_way.add_node(());
+        one_way.add_node(());
+        one_way.add_edge(0, 1, 1);
+        assert!(eccentricity(&one_way).is_none());
+    }
+
+    #[test]
+    fn weighted_triangle() {
+        let mut g = mtx::Graph::<u8, mtx::Undirected, mtx::Weighted>::default();
+        let ids: Vec<_> = (0..3).map(|i| g.add_node(i)).collect();
+        g.add_edge(ids[0], ids[1], 1);
+        g.add_edge(ids[1], ids[2], 1);
+        g.add_edge(ids[0], ids[2], 5);
+        let ecc = weighted_eccentricity(&g).unwrap();
+        assert_eq!(ecc.values(), &[2, 1, 2]);
+        assert_eq!(ecc.center(), vec![ids[1]]);
+        assert_eq!(ecc.diameter(), Some(2));
+    }
+}
@@ -0,0 +1,4 @@
+//! Distance based graph metrics: eccentricity, diameter, radius,
+//! center and periphery, exact or bounded.
+pub mod approximate;
+pub mod eccentricity;
@@ -5,6 +5,9 @@ use std::{
     ops::{Add, Mul},
 };
 
+use crate::adjacency::EdgeType;
+use crate::queue::QueueNode;
+
 use super::iter::{DFS, BFS};
 
 #[derive(Clone)]
@@ -63,6 +66,14 @@ where
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct GraphIdx(pub(crate) usize);
 
+impl From<GraphIdx> for usize {
+    /// The node's position, for indexing per-node
+    /// result vectors.
+    fn from(idx: GraphIdx) -> Self {
+        idx.0
+    }
+}
+
 impl<T: Hash + Eq + Clone> Graph<T> {
     pub fn add_edge(&mut self, x: GraphIdx, y: GraphIdx) {
         self.add_edge_weight(x, y, 1);
@@ -100,6 +111,22 @@ where
     }
 }
 
+impl<T, D, W> Graph<T, D, W>
+where
+    T: Hash + Eq + Clone,
+    D: Clone + EdgeType,
+    W: Clone,
+{
+    /// Sets an edge the way `add_edge` does for `D`, for
+    /// callers that are generic over the edge type.
+    pub(crate) fn insert_edge(&mut self, x: GraphIdx, y: GraphIdx, weight: usize) {
+        self.add_edge_weight(x, y, weight);
+        if !D::DIRECTED {
+            self.add_edge_weight(y, x, weight);
+        }
+    }
+}
+
 impl<T, D, W> Graph<T, D, W>
 where
     T: Hash + Eq + Clone,
@@ -155,12 +182,12 @@ where
         self.mtx[idx] > 0
     }
 
-    pub fn dfs(&self, start: GraphIdx) -> DFS<T, D, W> {
-        DFS::new(&self, start)
+    pub fn dfs(&self, start: GraphIdx) -> DFS<'_, T, D, W> {
+        DFS::new(self, start)
     }
 
-    pub fn bfs(&self, start: GraphIdx) -> BFS<T, D, W> {
-        BFS::new(&self, start)
+    pub fn bfs(&self, start: GraphIdx) -> BFS<'_, T, D, W> {
+        BFS::new(self, start)
     }
 
     /// Implementation of Dijkstra's path finding algorithm.
@@ -177,7 +204,7 @@ where
         max_cost: Option<i32>,
         target: Option<GraphIdx>,
     ) -> HashMap<GraphIdx, Option<GraphIdx>> {
-        let mut frontier = BinaryHeap::<QueueNode>::new();
+        let mut frontier = BinaryHeap::<QueueNode<GraphIdx, i32>>::new();
         frontier.push(QueueNode::new(start, 0));
 
         let mut came_from = HashMap::<GraphIdx, Option<GraphIdx>>::new();
@@ -203,7 +230,7 @@ where
                 let new_cost = cost_so_far.get(&current.idx).unwrap_or(&0) + edge;
                 let next_cost = *cost_so_far.get(&neighbor).unwrap_or(&0);
                 if (!cost_so_far.contains_key(&neighbor) || new_cost < next_cost)
-                    && max_cost.map_or(true, |max| new_cost <= max)
+                    && max_cost.is_none_or(|max| new_cost <= max)
                 {
                     cost_so_far.insert(neighbor, new_cost);
                     came_from.insert(neighbor, Some(current.idx));
@@ -245,9 +272,7 @@ where
             let idxx = GraphIdx(x);
             let a = calc_2d_to_1d(idxx, idxy, len);
             let b = calc_2d_to_1d(idxy, idxx, len);
-            let tmp = graph.mtx[a];
-            graph.mtx[a] = graph.mtx[b];
-            graph.mtx[b] = tmp;
+            graph.mtx.swap(a, b);
         }
     }
 }
@@ -270,30 +295,6 @@ where
     }
 }
 
-#[derive(PartialEq, Eq)]
-struct QueueNode {
-    idx: GraphIdx,
-    weight: i32,
-}
-
-impl QueueNode {
-    fn new(idx: GraphIdx, weight: i32) -> Self {
-        Self { idx, weight }
-    }
-}
-
-impl Ord for QueueNode {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.weight.cmp(&self.weight)
-    }
-}
-
-impl PartialOrd for QueueNode {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
 fn calc_2d_to_1d(x: GraphIdx, y: GraphIdx, len: usize) -> usize {
     // [0 0 1 0]
     // [0 0 0 0]
@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A combinatorial embedding of a planar graph: the clockwise
+/// order of the neighbours around every node. Loops and
+/// parallel edges of the input are not part of it.
+#[derive(Debug, Clone)]
+pub struct Embedding<I> {
+    pub(crate) ids: Vec<I>,
+    pub(crate) pos: HashMap<I, usize>,
+    /// Clockwise neighbour positions by position.
+    pub(crate) order: Vec<Vec<usize>>,
+}
+
+impl<I: Copy + Eq + Hash> Embedding<I> {
+    pub(crate) fn new(ids: Vec<I>, order: Vec<Vec<usize>>) -> Self {
+        let pos = ids.iter().enumerate().map(|(p, &id)| (id, p)).collect();
+        Self { ids, pos, order }
+    }
+
+    /// Neighbours of `node` in clockwise order.
+    pub fn clockwise(&self, node: I) -> Vec<I> {
+        self.order[self.pos[&node]]
+            .iter()
+            .map(|&p| self.ids[p])
+            .collect()
+    }
+
+    /// The faces of the embedding, each as the cycle of nodes
+    /// met walking along its boundary. A tree has one face
+    /// per component, with every edge walked twice.
+    /// Time complexity:
+    ///   O(n + m)
+    pub fn faces(&self) -> Vec<Vec<I>> {
+        // Slot of `v` in the rotation of `u`, per half edge.
+        let slot: HashMap<(usize, usize), usize> = self
+            .order
+            .iter()
+            .enumerate()
+            .flat_map(|(u, list)| list.iter().enumerate().map(move |(i, &v)| ((u, v), i)))
+            .collect();
+        let mut seen = vec![Vec::new(); self.order.len()];
+        for (u, list) in self.order.iter().enumerate() {
+            seen[u] = vec![false; list.len()];
+        }
+
+        let mut faces = Vec::new();
+        for start in 0..self.order.len() {
+            for i in 0..self.order[start].len() {
+                if seen[start][i] {
+                    continue;
+                }
+                let mut face = Vec::new();
+                let (mut u, mut i) = (start, i);
+                while !seen[u][i] {
+                    seen[u][i] = true;
+                    face.push(self.ids[u]);
+                    // Turn to the edge just counter-clockwise of
+                    // the one we arrived on.
+                    let v = self.order[u][i];
+                    let back = slot[&(v, u)];
+                    let deg = self.order[v].len();
+                    i = (back + deg - 1) % deg;
+                    u = v;
+                }
+                faces.push(face);
+            }
+        }
+
+        faces
+    }
+}
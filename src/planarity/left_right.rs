@@ -0,0 +1,596 @@
+use std::collections::HashMap;
+
+use crate::adjacency::{undirected_adjacency, without_loops, Adjacency};
+
+use super::embedding::Embedding;
+
+/// Outcome of a planarity test, with a certificate either way.
+#[derive(Debug, Clone)]
+pub enum Planarity<I> {
+    /// A planar embedding of the graph.
+    Planar(Embedding<I>),
+    /// The edges of a subdivision of K5 or K3,3 in the graph.
+    NonPlanar(Vec<(I, I)>),
+}
+
+impl<I> Planarity<I> {
+    pub fn is_planar(&self) -> bool {
+        matches!(self, Planarity::Planar(_))
+    }
+}
+
+/// Tests whether an undirected graph can be drawn in the plane
+/// without crossings. Directed edges are read both ways; loops
+/// and parallel edges are ignored.
+/// Time complexity:
+///   O(n + m)
+pub fn is_planar<G: Adjacency>(graph: &G) -> bool {
+    let adj = without_loops(undirected_adjacency(graph));
+    LeftRight::new(&adj).run(false).is_some()
+}
+
+/// Runs the left-right planarity test of de Fraysseix and
+/// Rosenstiehl, in the formulation of Brandes, and returns an
+/// embedding when the graph is planar.
+///
+/// For a non-planar graph a Kuratowski subgraph is extracted by
+/// dropping every edge whose removal keeps the rest non-planar;
+/// what remains is a minimal non-planar subgraph.
+/// Time complexity:
+///   O(n + m) for a planar graph, O(m * (n + m)) otherwise.
+pub fn planarity<G: Adjacency>(graph: &G) -> Planarity<G::NodeId> {
+    let adj = without_loops(undirected_adjacency(graph));
+    if let Some(order) = LeftRight::new(&adj).run(true) {
+        let ids = (0..graph.node_count()).map(|p| graph.node_id(p)).collect();
+        return Planarity::Planar(Embedding::new(ids, order));
+    }
+
+    let n = adj.len();
+    let mut edges: Vec<(usize, usize)> = adj
+        .iter()
+        .enumerate()
+        .flat_map(|(u, list)| list.iter().filter(move |&&v| u < v).map(move |&v| (u, v)))
+        .collect();
+    let mut i = 0;
+    while i < edges.len() {
+        let removed = edges.remove(i);
+        if is_planar_edges(n, &edges) {
+            edges.insert(i, removed);
+            i += 1;
+        }
+    }
+
+    Planarity::NonPlanar(
+        edges
+            .into_iter()
+            .map(|(u, v)| (graph.node_id(u), graph.node_id(v)))
+            .collect(),
+    )
+}
+
+fn is_planar_edges(n: usize, edges: &[(usize, usize)]) -> bool {
+    let mut adj = vec![Vec::new(); n];
+    for &(u, v) in edges {
+        adj[u].push(v);
+        adj[v].push(u);
+    }
+    LeftRight::new(&adj).run(false).is_some()
+}
+
+const NONE: usize = usize::MAX;
+
+/// Return edges of one side of a conflict pair: the edges
+/// with the lowest and the highest return points.
+#[derive(Debug, Clone, Copy, Default)]
+struct Interval {
+    low: Option<usize>,
+    high: Option<usize>,
+}
+
+impl Interval {
+    fn is_empty(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ConflictPair {
+    left: Interval,
+    right: Interval,
+}
+
+impl ConflictPair {
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+    }
+}
+
+/// State of the left-right test. Every undirected edge gets an
+/// id and is oriented by the first depth-first search; all per
+/// edge tables are indexed by that id.
+struct LeftRight {
+    /// `(neighbour, edge id)` per node.
+    adj: Vec<Vec<(usize, usize)>>,
+    source: Vec<usize>,
+    target: Vec<usize>,
+    oriented: Vec<bool>,
+    out: Vec<Vec<usize>>,
+    height: Vec<usize>,
+    parent_edge: Vec<Option<usize>>,
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting_depth: Vec<i64>,
+    reference: Vec<Option<usize>>,
+    side: Vec<i64>,
+    lowpt_edge: Vec<usize>,
+    stack_bottom: Vec<usize>,
+    stack: Vec<ConflictPair>,
+    roots: Vec<usize>,
+}
+
+impl LeftRight {
+    fn new(simple: &[Vec<usize>]) -> Self {
+        let n = simple.len();
+        let mut adj = vec![Vec::new(); n];
+        let mut m = 0;
+        for (u, list) in simple.iter().enumerate() {
+            for &v in list.iter().filter(|&&v| u < v) {
+                adj[u].push((v, m));
+                adj[v].push((u, m));
+                m += 1;
+            }
+        }
+
+        Self {
+            adj,
+            source: vec![NONE; m],
+            target: vec![NONE; m],
+            oriented: vec![false; m],
+            out: vec![Vec::new(); n],
+            height: vec![NONE; n],
+            parent_edge: vec![None; n],
+            lowpt: vec![0; m],
+            lowpt2: vec![0; m],
+            nesting_depth: vec![0; m],
+            reference: vec![None; m],
+            side: vec![1; m],
+            lowpt_edge: vec![NONE; m],
+            stack_bottom: vec![0; m],
+            stack: Vec::new(),
+            roots: Vec::new(),
+        }
+    }
+
+    /// Runs the test, returning the clockwise rotation of every
+    /// node when the graph is planar. The rotation is left empty
+    /// unless `embed` is set.
+    fn run(mut self, embed: bool) -> Option<Vec<Vec<usize>>> {
+        let n = self.adj.len();
+        let m = self.source.len();
+        if n > 2 && m > 3 * n - 6 {
+            return None;
+        }
+
+        for v in 0..n {
+            if self.height[v] == NONE {
+                self.height[v] = 0;
+                self.roots.push(v);
+                self.orient(v);
+            }
+        }
+        self.sort_out_edges();
+        for i in 0..self.roots.len() {
+            if !self.test(self.roots[i]) {
+                return None;
+            }
+        }
+        if !embed {
+            return Some(Vec::new());
+        }
+
+        for e in 0..m {
+            self.nesting_depth[e] *= self.sign(e);
+        }
+        self.sort_out_edges();
+        let mut rotation = Rotation::new(n);
+        for v in 0..n {
+            let mut previous = None;
+            for &e in &self.out[v] {
+                let w = self.target[e];
+                rotation.insert_cw(v, w, previous);
+                previous = Some(w);
+            }
+        }
+        let mut left_ref = vec![NONE; n];
+        let mut right_ref = vec![NONE; n];
+        for i in 0..self.roots.len() {
+            self.embed(self.roots[i], &mut rotation, &mut left_ref, &mut right_ref);
+        }
+
+        Some(rotation.into_order())
+    }
+
+    fn sort_out_edges(&mut self) {
+        let depth = &self.nesting_depth;
+        for list in self.out.iter_mut() {
+            list.sort_by_key(|&e| depth[e]);
+        }
+    }
+
+    /// First DFS: orients the edges and computes lowpoints and
+    /// nesting depths.
+    fn orient(&mut self, v: usize) {
+        let parent = self.parent_edge[v];
+        for i in 0..self.adj[v].len() {
+            let (w, e) = self.adj[v][i];
+            if self.oriented[e] {
+                continue;
+            }
+            self.oriented[e] = true;
+            self.source[e] = v;
+            self.target[e] = w;
+            self.out[v].push(e);
+            self.lowpt[e] = self.height[v];
+            self.lowpt2[e] = self.height[v];
+            if self.height[w] == NONE {
+                self.parent_edge[w] = Some(e);
+                self.height[w] = self.height[v] + 1;
+                self.orient(w);
+            } else {
+                self.lowpt[e] = self.height[w];
+            }
+
+            self.nesting_depth[e] = 2 * self.lowpt[e] as i64;
+            if self.lowpt2[e] < self.height[v] {
+                self.nesting_depth[e] += 1;
+            }
+
+            if let Some(p) = parent {
+                if self.lowpt[e] < self.lowpt[p] {
+                    self.lowpt2[p] = self.lowpt[p].min(self.lowpt2[e]);
+                    self.lowpt[p] = self.lowpt[e];
+                } else if self.lowpt[e] > self.lowpt[p] {
+                    self.lowpt2[p] = self.lowpt2[p].min(self.lowpt[e]);
+                } else {
+                    self.lowpt2[p] = self.lowpt2[p].min(self.lowpt2[e]);
+                }
+            }
+        }
+    }
+
+    /// Second DFS: checks the left-right constraints of the
+    /// return edges, visiting children by nesting depth.
+    fn test(&mut self, v: usize) -> bool {
+        let parent = self.parent_edge[v];
+        for i in 0..self.out[v].len() {
+            let e = self.out[v][i];
+            let w = self.target[e];
+            self.stack_bottom[e] = self.stack.len();
+            if self.parent_edge[w] == Some(e) {
+                if !self.test(w) {
+                    return false;
+                }
+            } else {
+                self.lowpt_edge[e] = e;
+                self.stack.push(ConflictPair {
+                    left: Interval::default(),
+                    right: Interval { low: Some(e), high: Some(e) },
+                });
+            }
+
+            // Only a non-root node has return edges below it.
+            if self.lowpt[e] < self.height[v] {
+                let p = parent.expect("return edge below the root");
+                if i == 0 {
+                    self.lowpt_edge[p] = self.lowpt_edge[e];
+                } else if !self.add_constraints(e, p) {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(p) = parent {
+            self.remove_back_edges(p);
+        }
+        true
+    }
+
+    fn conflicting(&self, interval: Interval, e: usize) -> bool {
+        interval.high.is_some_and(|h| self.lowpt[h] > self.lowpt[e])
+    }
+
+    fn lowest(&self, pair: &ConflictPair) -> usize {
+        let low = |i: Interval| self.lowpt[i.low.unwrap()];
+        if pair.left.is_empty() {
+            low(pair.right)
+        } else if pair.right.is_empty() {
+            low(pair.left)
+        } else {
+            low(pair.left).min(low(pair.right))
+        }
+    }
+
+    fn add_constraints(&mut self, e: usize, parent: usize) -> bool {
+        let mut pair = ConflictPair::default();
+
+        // Merge the return edges of `e` into the right side.
+        loop {
+            let mut q = self.stack.pop().unwrap();
+            if !q.left.is_empty() {
+                q.swap();
+            }
+            if !q.left.is_empty() {
+                return false;
+            }
+            let q_low = q.right.low.unwrap();
+            if self.lowpt[q_low] > self.lowpt[parent] {
+                if pair.right.is_empty() {
+                    pair.right = q.right;
+                } else {
+                    self.reference[pair.right.low.unwrap()] = q.right.high;
+                }
+                pair.right.low = q.right.low;
+            } else {
+                self.reference[q_low] = Some(self.lowpt_edge[parent]);
+            }
+            if self.stack.len() == self.stack_bottom[e] {
+                break;
+            }
+        }
+
+        // Merge the conflicting return edges of earlier siblings
+        // into the left side.
+        while let Some(&top) = self.stack.last() {
+            if !self.conflicting(top.left, e) && !self.conflicting(top.right, e) {
+                break;
+            }
+            let mut q = self.stack.pop().unwrap();
+            if self.conflicting(q.right, e) {
+                q.swap();
+            }
+            if self.conflicting(q.right, e) {
+                return false;
+            }
+            if let Some(low) = pair.right.low {
+                self.reference[low] = q.right.high;
+            }
+            if q.right.low.is_some() {
+                pair.right.low = q.right.low;
+            }
+            if pair.left.is_empty() {
+                pair.left = q.left;
+            } else {
+                self.reference[pair.left.low.unwrap()] = q.left.high;
+            }
+            pair.left.low = q.left.low;
+        }
+
+        if !pair.left.is_empty() || !pair.right.is_empty() {
+            self.stack.push(pair);
+        }
+        true
+    }
+
+    /// Drops the return edges ending at the source of `e` and
+    /// fixes which return edge decides the side of `e`.
+    fn remove_back_edges(&mut self, e: usize) {
+        let u = self.source[e];
+        while let Some(top) = self.stack.last() {
+            if self.lowest(top) != self.height[u] {
+                break;
+            }
+            let pair = self.stack.pop().unwrap();
+            if let Some(low) = pair.left.low {
+                self.side[low] = -1;
+            }
+        }
+
+        if let Some(mut pair) = self.stack.pop() {
+            while let Some(high) = pair.left.high.filter(|&h| self.target[h] == u) {
+                pair.left.high = self.reference[high];
+            }
+            if pair.left.high.is_none() {
+                if let Some(low) = pair.left.low.take() {
+                    self.reference[low] = pair.right.low;
+                    self.side[low] = -1;
+                }
+            }
+            while let Some(high) = pair.right.high.filter(|&h| self.target[h] == u) {
+                pair.right.high = self.reference[high];
+            }
+            if pair.right.high.is_none() {
+                if let Some(low) = pair.right.low.take() {
+                    self.reference[low] = pair.left.low;
+                    self.side[low] = -1;
+                }
+            }
+            self.stack.push(pair);
+        }
+
+        if self.lowpt[e] < self.height[u] {
+            let top = self.stack.last().unwrap();
+            let (hl, hr) = (top.left.high, top.right.high);
+            self.reference[e] = match (hl, hr) {
+                (Some(l), Some(r)) if self.lowpt[l] > self.lowpt[r] => hl,
+                (Some(_), None) => hl,
+                _ => hr,
+            };
+        }
+    }
+
+    /// Resolves the final side of `e` by following its chain of
+    /// references, compressing the chain on the way.
+    fn sign(&mut self, e: usize) -> i64 {
+        let mut chain = vec![e];
+        while let Some(r) = self.reference[*chain.last().unwrap()] {
+            chain.push(r);
+        }
+        for i in (0..chain.len() - 1).rev() {
+            let (x, next) = (chain[i], chain[i + 1]);
+            self.side[x] *= self.side[next];
+            self.reference[x] = None;
+        }
+        self.side[e]
+    }
+
+    /// Third DFS: places every back edge in the rotation of the
+    /// node it returns to, on the side chosen by the test.
+    fn embed(&self, v: usize, rotation: &mut Rotation, left_ref: &mut [usize], right_ref: &mut [usize]) {
+        for &e in &self.out[v] {
+            let w = self.target[e];
+            if self.parent_edge[w] == Some(e) {
+                rotation.insert_first(w, v);
+                left_ref[v] = w;
+                right_ref[v] = w;
+                self.embed(w, rotation, left_ref, right_ref);
+            } else if self.side[e] == 1 {
+                rotation.insert_cw(w, v, Some(right_ref[w]));
+            } else {
+                rotation.insert_ccw(w, v, Some(left_ref[w]));
+                left_ref[w] = v;
+            }
+        }
+    }
+}
+
+/// Cyclic neighbour orders under construction, as doubly
+/// linked lists keyed by half edge.
+struct Rotation {
+    /// `(clockwise, counter-clockwise)` neighbour of each
+    /// half edge.
+    links: HashMap<(usize, usize), (usize, usize)>,
+    first: Vec<Option<usize>>,
+    degree: Vec<usize>,
+}
+
+impl Rotation {
+    fn new(n: usize) -> Self {
+        Self {
+            links: HashMap::new(),
+            first: vec![None; n],
+            degree: vec![0; n],
+        }
+    }
+
+    /// Inserts `w` right after `reference` in the clockwise
+    /// order around `v`, or as its only neighbour.
+    fn insert_cw(&mut self, v: usize, w: usize, reference: Option<usize>) {
+        self.degree[v] += 1;
+        let r = match reference {
+            Some(r) => r,
+            None => {
+                self.links.insert((v, w), (w, w));
+                self.first[v] = Some(w);
+                return;
+            }
+        };
+        let cw = self.links[&(v, r)].0;
+        self.links.get_mut(&(v, r)).unwrap().0 = w;
+        self.links.get_mut(&(v, cw)).unwrap().1 = w;
+        self.links.insert((v, w), (cw, r));
+    }
+
+    /// Inserts `w` right before `reference`, taking its place
+    /// as first neighbour if it held it.
+    fn insert_ccw(&mut self, v: usize, w: usize, reference: Option<usize>) {
+        match reference {
+            Some(r) => {
+                let ccw = self.links[&(v, r)].1;
+                self.insert_cw(v, w, Some(ccw));
+                if self.first[v] == Some(r) {
+                    self.first[v] = Some(w);
+                }
+            }
+            None => self.insert_cw(v, w, None),
+        }
+    }
+
+    fn insert_first(&mut self, v: usize, w: usize) {
+        self.insert_ccw(v, w, self.first[v]);
+    }
+
+    fn into_order(self) -> Vec<Vec<usize>> {
+        (0..self.first.len())
+            .map(|v| {
+                let mut order = Vec::with_capacity(self.degree[v]);
+                let mut cur = self.first[v];
+                while let Some(w) = cur {
+                    order.push(w);
+                    let next = self.links[&(v, w)].0;
+                    cur = if order.len() < self.degree[v] { Some(next) } else { None };
+                }
+                order
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Graph, Undirected};
+
+    use super::*;
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Graph<(), Undirected, ()> {
+        let mut g = Graph::<_, Undirected, ()>::new();
+        for _ in 0..n {
+            g.add_node(());
+        }
+        for &(a, b) in edges {
+            g.add_edge(a, b, ());
+        }
+        g
+    }
+
+    fn complete(n: usize) -> Vec<(usize, usize)> {
+        (0..n).flat_map(|a| (a + 1..n).map(move |b| (a, b))).collect()
+    }
+
+    #[test]
+    fn kuratowski_graphs() {
+        let k5 = undirected(5, &complete(5));
+        assert!(!is_planar(&k5));
+        match planarity(&k5) {
+            Planarity::NonPlanar(edges) => assert_eq!(edges.len(), 10),
+            Planarity::Planar(_) => panic!("K5 is not planar"),
+        }
+
+        let k33: Vec<_> = (0..3).flat_map(|a| (3..6).map(move |b| (a, b))).collect();
+        assert!(!is_planar(&undirected(6, &k33)));
+
+        // K5 minus an edge is planar; a subdivided K3,3 with
+        // extra chords still yields exactly its nine paths.
+        assert!(is_planar(&undirected(5, &complete(5)[1..])));
+        let mut subdivided: Vec<_> = k33
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &(a, b))| vec![(a, i + 6), (i + 6, b)])
+            .collect();
+        subdivided.extend([(0, 1), (1, 2)]);
+        match planarity(&undirected(15, &subdivided)) {
+            Planarity::NonPlanar(edges) => {
+                assert_eq!(edges.len(), 18);
+                assert!(!edges.contains(&(0, 1)) && !edges.contains(&(1, 2)));
+            }
+            Planarity::Planar(_) => panic!("contains K3,3"),
+        }
+    }
+
+    #[test]
+    fn embedding_satisfies_euler() {
+        // Octahedron: 6 nodes, 12 edges, 8 faces.
+        let octahedron: Vec<_> = complete(6)
+            .into_iter()
+            .filter(|&(a, b)| b != a + 3)
+            .collect();
+        match planarity(&undirected(6, &octahedron)) {
+            Planarity::Planar(embedding) => {
+                let faces = embedding.faces();
+                assert_eq!(faces.len(), 8);
+                assert!(faces.iter().all(|f| f.len() == 3));
+                assert_eq!(embedding.clockwise(0).len(), 4);
+            }
+            Planarity::NonPlanar(_) => panic!("octahedron is planar"),
+        }
+    }
+}
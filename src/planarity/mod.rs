@@ -0,0 +1,4 @@
+//! Planarity testing with combinatorial embeddings and
+//! Kuratowski certificates.
+pub mod embedding;
+pub mod left_right;
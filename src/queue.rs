@@ -0,0 +1,28 @@
+use std::cmp::Ordering;
+
+/// Priority queue entry for min-heap style searches over a
+/// `BinaryHeap`. The ordering is reversed on the weight so the
+/// cheapest entry is popped first.
+#[derive(PartialEq, Eq)]
+pub(crate) struct QueueNode<I, C> {
+    pub(crate) idx: I,
+    pub(crate) weight: C,
+}
+
+impl<I, C> QueueNode<I, C> {
+    pub(crate) fn new(idx: I, weight: C) -> Self {
+        Self { idx, weight }
+    }
+}
+
+impl<I: Eq, C: Ord> Ord for QueueNode<I, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.weight.cmp(&self.weight)
+    }
+}
+
+impl<I: Eq, C: Ord> PartialOrd for QueueNode<I, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
@@ -19,11 +19,11 @@ pub fn purdoms<V, D, E>(graph: &mut Graph<V, D, E>) -> TransitiveClosureMtx
     //    transitive closure of 𝐺̃ .
 
     // 1.
-    let sccs = Tarjan::new(&graph).sccs();
+    let sccs = Tarjan::new(graph).sccs();
     replace_sccs(graph, &sccs);
 
     // 2.
-    let topo = topo_sort(&graph);
+    let _topo = topo_sort(graph);
     unimplemented!()
 }
 
@@ -31,7 +31,7 @@ pub fn replace_sccs<V, D, E>(
     graph: &mut Graph<V, D, E>,
     sccs: &[usize],
 ) {
-    let map = gather_sccs(&sccs);
+    let map = gather_sccs(sccs);
     for (keep, replaces) in map.iter() {
         graph.replace_nodes(replaces, *keep);
     }
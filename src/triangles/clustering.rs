@@ -0,0 +1,90 @@
+use crate::adjacency::{undirected_adjacency, without_loops};
+
+use super::count::Triangles;
+
+/// Local clustering coefficient of every node: the fraction of
+/// pairs of its neighbours that are adjacent themselves. Nodes
+/// with fewer than two neighbours score zero.
+pub fn local_clustering<G: Triangles>(graph: &G) -> Vec<f64> {
+    let triangles = graph.node_triangles();
+    degrees(graph)
+        .into_iter()
+        .zip(triangles)
+        .map(|(d, t)| {
+            if d < 2 {
+                0.0
+            } else {
+                2.0 * t as f64 / (d * (d - 1)) as f64
+            }
+        })
+        .collect()
+}
+
+/// Mean of the local clustering coefficients over all nodes.
+pub fn average_clustering<G: Triangles>(graph: &G) -> f64 {
+    let local = local_clustering(graph);
+    if local.is_empty() {
+        return 0.0;
+    }
+    local.iter().sum::<f64>() / local.len() as f64
+}
+
+/// Transitivity, or global clustering coefficient: three times
+/// the number of triangles over the number of connected triples
+/// (paths of length two).
+pub fn transitivity<G: Triangles>(graph: &G) -> f64 {
+    let triangles: usize = graph.node_triangles().iter().sum();
+    let triples: usize = degrees(graph)
+        .into_iter()
+        .map(|d| d * d.saturating_sub(1) / 2)
+        .sum();
+    if triples == 0 {
+        return 0.0;
+    }
+    // Summing per node counts every triangle three times.
+    triangles as f64 / triples as f64
+}
+
+fn degrees<G: Triangles>(graph: &G) -> Vec<usize> {
+    without_loops(undirected_adjacency(graph))
+        .iter()
+        .map(Vec::len)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::list_graph::graph::{Graph, Undirected};
+    use crate::mtx_graph::graph as mtx;
+
+    use super::*;
+
+    #[test]
+    fn triangle_with_pendant() {
+        // Triangle 0-1-2 plus an edge 2-3.
+        let mut g = Graph::<_, Undirected, ()>::new();
+        for _ in 0..4 {
+            g.add_node(());
+        }
+        for (a, b) in [(0, 1), (1, 2), (2, 0), (2, 3)] {
+            g.add_edge(a, b, ());
+        }
+        assert_eq!(local_clustering(&g), vec![1.0, 1.0, 1.0 / 3.0, 0.0]);
+        assert!((average_clustering(&g) - (7.0 / 3.0) / 4.0).abs() < 1e-12);
+        // One triangle, 1 + 1 + 3 connected triples.
+        assert!((transitivity(&g) - 3.0 / 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn complete_graph() {
+        let mut g = mtx::Graph::<u8, mtx::Undirected, mtx::Unweighted>::default();
+        let ids: Vec<_> = (0..5).map(|i| g.add_node(i)).collect();
+        for a in 0..5 {
+            for b in a + 1..5 {
+                g.add_edge(ids[a], ids[b]);
+            }
+        }
+        assert_eq!(local_clustering(&g), vec![1.0; 5]);
+        assert_eq!(transitivity(&g), 1.0);
+    }
+}
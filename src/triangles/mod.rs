@@ -0,0 +1,3 @@
+//! Triangle counts and the clustering coefficients built on them.
+pub mod clustering;
+pub mod count;